@@ -0,0 +1,126 @@
+// Reads the resource limits of the cgroup we're running in, for when we're inside of a container
+// Supports both cgroup v2 and the v1 controllers
+// https://docs.kernel.org/admin-guide/cgroup-v2.html
+// https://docs.kernel.org/admin-guide/cgroup-v1/index.html
+
+use std::path::{Path, PathBuf};
+
+use crate::util;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+// v1 uses the max i64 rounded down to the page size to say there's no limit
+// The page size can be anything from 4K to 64K (or higher), so anything this close is treated as unlimited
+const CGROUP_V1_UNLIMITED: u64 = 0x7FFF_FFFF_0000_0000;
+
+pub struct CgroupMemory {
+    pub limit_kb: Option<u64>,
+    pub usage_kb: Option<u64>
+}
+
+/// Finds the memory limit and current usage of our cgroup
+/// Returns None if we aren't in a cgroup with the memory controller available
+pub fn get_memory() -> Option<CgroupMemory> {
+    let (v2, dirs) = find_cgroup_dirs("memory")?;
+    let (limit_file, usage_file): (&str, &str) = if v2 {
+        ("memory.max", "memory.current")
+    } else {
+        ("memory.limit_in_bytes", "memory.usage_in_bytes")
+    };
+
+    // Limits are inherited, so the effective limit is the lowest one in the hierarchy
+    let limit_bytes: Option<u64> = dirs.iter()
+        .filter_map(|x| read_value(&x.join(limit_file)))
+        .filter(|x| *x < CGROUP_V1_UNLIMITED)
+        .min();
+    // Where as usage is only relevant for our own cgroup
+    let usage_bytes: Option<u64> = dirs.iter().find_map(|x| read_value(&x.join(usage_file)));
+    if limit_bytes.is_none() && usage_bytes.is_none() {
+        return None;
+    }
+
+    Some(CgroupMemory {
+        limit_kb: limit_bytes.map(|x| x / 1000),
+        usage_kb: usage_bytes.map(|x| x / 1000)
+    })
+}
+
+/// Finds how many CPU's worth of time our cgroup is allowed to use, e.g 1.5
+/// Returns None if there's no limit
+#[allow(clippy::cast_precision_loss)]
+pub fn get_cpu_limit() -> Option<f64> {
+    let (v2, dirs) = find_cgroup_dirs("cpu")?;
+
+    dirs.iter()
+        .filter_map(|dir| {
+            let (quota, period): (u64, u64) = if v2 {
+                // "$MAX $PERIOD", where $MAX may be "max"
+                let contents: String = util::file_read(&dir.join("cpu.max")).ok()?;
+                let mut values = contents.split_whitespace();
+                (values.next()?.parse().ok()?, values.next()?.parse().ok()?)
+            } else {
+                // Quota is -1 when there's no limit, which fails to parse here and gets skipped
+                (read_value(&dir.join("cpu.cfs_quota_us"))?, read_value(&dir.join("cpu.cfs_period_us"))?)
+            };
+            if period == 0 {
+                return None;
+            }
+            Some(quota as f64 / period as f64)
+        })
+        .min_by(f64::total_cmp)
+}
+
+// Finds the directories for the cgroup that we're in for the controller given, going from our own
+// cgroup up through it's parents
+// Also gives back if it's cgroup v2 or not
+fn find_cgroup_dirs(controller: &str) -> Option<(bool, Vec<PathBuf>)> {
+    // Each line is "$ID:$CONTROLLERS:$PATH"
+    // v2 is always "0::$PATH", while v1 has a line per hierarchy with the controllers it has
+    let contents: String = util::file_read(Path::new("/proc/self/cgroup")).ok()?;
+
+    let mut found: Option<(bool, PathBuf, &str)> = None;
+    for line in contents.lines() {
+        let mut values = line.splitn(3, ':');
+        let (Some(_), Some(controllers), Some(path)) = (values.next(), values.next(), values.next()) else {
+            continue;
+        };
+
+        if controllers.is_empty() {
+            // Hybrid systems may have both, in which case the v1 controller is the one in use
+            // They also mount v2 in a sub-directory rather than at the root
+            if found.is_none() {
+                let root: PathBuf = if Path::new(CGROUP_ROOT).join("cgroup.controllers").exists() {
+                    PathBuf::from(CGROUP_ROOT)
+                } else {
+                    Path::new(CGROUP_ROOT).join("unified")
+                };
+                found = Some((true, root, path));
+            }
+        } else if controllers.split(',').any(|x| x == controller) {
+            found = Some((false, Path::new(CGROUP_ROOT).join(controllers), path));
+            break;
+        }
+    }
+    let (v2, root, path) = found?;
+
+    // Inside of a container we may be given a path from the host's point of view that doesn't
+    // exist for us, in which case we're just at the root of the mount
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    let mut current: PathBuf = root.join(path.trim_start_matches('/'));
+    while current.starts_with(&root) {
+        if current.is_dir() {
+            dirs.push(current.clone());
+        }
+        if !current.pop() {
+            break;
+        }
+    }
+
+    if dirs.is_empty() {
+        return None;
+    }
+    Some((v2, dirs))
+}
+
+fn read_value(path: &Path) -> Option<u64> {
+    util::file_read(path).ok()?.trim().parse::<u64>().ok()
+}
@@ -0,0 +1,17 @@
+// Reads the parsed DMI values the kernel exposes
+// Most of these are world readable, unlike the raw SMBIOS table in smbios.rs
+
+use std::path::Path;
+
+use crate::util;
+
+const DMI_ID_DIR: &str = "/sys/devices/virtual/dmi/id";
+
+/// Reads a single value from `/sys/devices/virtual/dmi/id`, e.g `board_vendor` or `bios_date`
+pub fn read_value(name: &str) -> Result<String, String> {
+    let path: &Path = &Path::new(DMI_ID_DIR).join(name);
+    match util::file_read(path) {
+        Ok(r) => Ok(r.trim().to_string()),
+        Err(e) => Err(format!("Can't read from {} - {e}", path.display())),
+    }
+}
@@ -0,0 +1,137 @@
+// Looks up vendor/device names from the pci.ids and usb.ids databases
+// https://man.archlinux.org/man/core/pciutils/pci.ids.5.en
+//
+// To prevent having to relicence to GPL I don't distribute a copy of these, and simply have to
+// rely on the host's copy. Problem then becomes that different distros seem to place these files
+// in different places
+// I'll try to find them in as many places as possible but ultimately can't cover every place. If
+// you know the places, make a PR/Issue and i'll add it in. Fucking hate licences that work
+// like this but oh well.
+
+use std::{fs, path::{Path, PathBuf}};
+
+use crate::{common_sources::ids_index::{IdsFormat, IdsIndex}, util};
+
+pub const PCI_IDS_PATHS: &[&str] = &[
+    "/usr/share/hwdata/pci.ids",
+    "/usr/share/misc/pci.ids",
+    "/usr/share/pci.ids"
+];
+pub const USB_IDS_PATHS: &[&str] = &[
+    "/usr/share/hwdata/usb.ids",
+    "/usr/share/misc/usb.ids",
+    "/var/lib/usbutils/usb.ids"
+];
+
+/// Finds pci.ids, checking `custom_path` (the `pci_ids_path` config option) before anywhere else
+pub fn find_pci_ids(custom_path: Option<&str>) -> Option<PathBuf> {
+    find_ids_file(custom_path, PCI_IDS_PATHS, "lspci", "pci.ids")
+}
+/// Finds usb.ids
+pub fn find_usb_ids() -> Option<PathBuf> {
+    find_ids_file(None, USB_IDS_PATHS, "lsusb", "usb.ids")
+}
+fn find_ids_file(custom_path: Option<&str>, paths: &[&str], command: &str, file_name: &str) -> Option<PathBuf> {
+    if let Some(custom_path) = custom_path {
+        let path: PathBuf = PathBuf::from(custom_path);
+        if path.exists() {
+            return Some(path);
+        }
+    }
+    if let Some(path) = util::find_first_path_exists(paths.iter().map(Path::new).collect()) {
+        return Some(path.to_path_buf());
+    }
+
+    // NixOS has none of the above, instead each package lives in it's own directory in the store.
+    // Find whatever pciutils/usbutils the user has, and work from there.
+    let command_path: PathBuf = fs::canonicalize(which::which(command).ok()?).ok()?;
+    // /nix/store/...-pciutils-3.13.0/bin/lspci -> /nix/store/...-pciutils-3.13.0
+    if let Some(package_path) = command_path.parent().and_then(Path::parent) {
+        let package_paths: Vec<PathBuf> = vec![
+            package_path.join("share").join(file_name),
+            package_path.join("share/hwdata").join(file_name)
+        ];
+        if let Some(path) = util::find_first_pathbuf_exists(package_paths) {
+            return Some(path);
+        }
+    }
+    // Otherwise it's been built against hwdata, which is a seperate package, and the path to it is
+    // baked into the binary
+    find_path_in_binary(&command_path, file_name)
+}
+// Pulls out the first absolute path in the binary ending in file_name
+fn find_path_in_binary(binary: &Path, file_name: &str) -> Option<PathBuf> {
+    let contents: Vec<u8> = fs::read(binary).ok()?;
+    let needle: &[u8] = file_name.as_bytes();
+    let mut search_from: usize = 0;
+    while let Some(pos) = contents[search_from..].windows(needle.len()).position(|x| x == needle) {
+        let end: usize = search_from + pos + needle.len();
+        search_from = end;
+
+        // Walk back to the start of the string
+        let start: usize = contents[..end].iter()
+            .rposition(|x| !x.is_ascii_graphic())
+            .map_or(0, |x| x + 1);
+        let Ok(path) = std::str::from_utf8(&contents[start..end]) else {
+            continue;
+        };
+        if !path.starts_with('/') {
+            continue;
+        }
+        let path: PathBuf = PathBuf::from(path);
+        if path.exists() {
+            return Some(path);
+        }
+    }
+
+    None
+}
+
+/// Opens the ids file at `ids_path` for the lookups below
+/// Modules should only do this once per run, as building the index without a cache dir means
+/// parsing the whole file.
+pub fn open_ids(ids_path: &Path) -> Result<IdsIndex, String> {
+    IdsIndex::open(ids_path, IdsFormat::Hwdata)
+}
+
+/// Finds the vendor and device name for a pair of IDs
+/// Both files use the same layout, so this works for either pci.ids or usb.ids.
+/// If the device can't be found, it's ID is given back instead.
+pub fn search_ids(ids: &IdsIndex, vendor: &str, device: &str) -> (String, String) {
+    let vendor_result: String = ids.vendor(vendor).unwrap_or_default();
+    let device_result: String = ids.device(vendor, device).unwrap_or(device.to_string());
+
+    (vendor_result, device_result)
+}
+
+/// Finds the name of a PCI device class from the end of pci.ids, e.g 02 00 -> Ethernet controller
+/// Gives back the base class's name if the subclass has none.
+pub fn search_pci_class(ids: &IdsIndex, class: &str, subclass: &str) -> Option<String> {
+    ids.subclass(class, subclass).or_else(|| ids.class(class))
+}
+
+/// Gives a vendor and device name when there's no pci.ids to get them from, or it doesn't know the
+/// device. Uses the driver's name if there is one, otherwise just the IDs.
+pub fn fallback_pci_names(vendor: &str, device: &str, driver: Option<&str>) -> (String, String) {
+    // Just the ones you're likely to find a GPU from
+    let vendor_name: String = match vendor.to_lowercase().as_str() {
+        "1002" | "1022" => "AMD".to_string(),
+        "10de" => "NVIDIA".to_string(),
+        "8086" => "Intel".to_string(),
+        "1a03" => "ASPEED".to_string(),
+        "102b" => "Matrox".to_string(),
+        "5143" => "Qualcomm".to_string(),
+        "1af4" => "Red Hat, Inc.".to_string(),
+        "1234" => "QEMU".to_string(),
+        "15ad" => "VMware".to_string(),
+        "80ee" => "VirtualBox".to_string(),
+        "1414" => "Microsoft Corporation".to_string(),
+        _ => vendor.to_string()
+    };
+    let device_name: String = match driver {
+        Some(driver) => format!("{driver} ({device})"),
+        None => device.to_string()
+    };
+
+    (vendor_name, device_name)
+}
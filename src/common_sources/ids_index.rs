@@ -0,0 +1,350 @@
+// A binary index over the pci.ids/usb.ids/amdgpu.ids databases, stored in the cache dir
+// These files are multiple megabytes of text, and scanning through them on every run was
+// dominating the GPU module's time. Instead they're parsed once into a sorted table that can be
+// binary searched straight from disk, and only rebuilt when the source file changes.
+//
+// Layout, all little endian;
+// - Header: magic (8), source size (8), source mtime seconds (8), source mtime nanoseconds (4), entry count (4)
+// - Entries, sorted by key: key (8), string offset (4), string length (4)
+// - Strings, one after another
+
+use std::{cmp::Ordering, env, fs::{self, File}, io::{BufRead, BufReader}, os::unix::fs::{FileExt, MetadataExt}, path::{Path, PathBuf}};
+
+const INDEX_MAGIC: &[u8; 8] = b"CFIDS001";
+const HEADER_LEN: u64 = 32;
+const ENTRY_LEN: u64 = 16;
+type Header = [u8; 32];
+
+// Keys are built as (first ID << 24) | (second ID << 8) | tag
+const TAG_VENDOR: u64 = 0;
+const TAG_DEVICE: u64 = 1;
+const TAG_CLASS: u64 = 2;
+const TAG_SUBCLASS: u64 = 3;
+const TAG_AMDGPU: u64 = 4;
+
+/// The layout of the source file being indexed
+#[derive(Clone, Copy)]
+pub enum IdsFormat {
+    /// pci.ids and usb.ids
+    Hwdata,
+    /// libdrm's amdgpu.ids
+    Amdgpu
+}
+
+pub struct IdsIndex {
+    storage: IndexStorage
+}
+enum IndexStorage {
+    File(File, u32),
+    // When the index can't be written out, e.g a read-only home
+    Memory(Vec<(u64, String)>)
+}
+impl IdsIndex {
+    /// Opens the index for `source`, building it first if it doesn't exist yet or `source` has
+    /// changed since it was built.
+    pub fn open(source: &Path, format: IdsFormat) -> Result<IdsIndex, String> {
+        let file_name: String = source.file_name().map_or("ids".to_string(), |x| x.to_string_lossy().to_string());
+        IdsIndex::open_at(source, get_cache_dir().map(|x| x.join(format!("{file_name}.index"))).as_deref(), format)
+    }
+    fn open_at(source: &Path, index_path: Option<&Path>, format: IdsFormat) -> Result<IdsIndex, String> {
+        let metadata: fs::Metadata = match fs::metadata(source) {
+            Ok(r) => r,
+            Err(e) => return Err(format!("Can't read from {} - {e}", source.display())),
+        };
+        let header: Header = build_header(&metadata, 0);
+
+        if let Some(index_path) = index_path {
+            if let Some(r) = IdsIndex::open_existing(index_path, &header) {
+                return Ok(r);
+            }
+        }
+
+        let entries: Vec<(u64, String)> = match format {
+            IdsFormat::Hwdata => parse_hwdata(source)?,
+            IdsFormat::Amdgpu => parse_amdgpu(source)?,
+        };
+        if let Some(index_path) = index_path {
+            // Not being able to cache it isn't the end of the world
+            let _ = write_index(index_path, &metadata, &entries);
+        }
+
+        Ok(IdsIndex {
+            storage: IndexStorage::Memory(entries)
+        })
+    }
+    fn open_existing(index_path: &Path, header: &Header) -> Option<IdsIndex> {
+        let file: File = File::open(index_path).ok()?;
+        let mut existing_header: Header = [0; 32];
+        file.read_exact_at(&mut existing_header, 0).ok()?;
+        // Everything bar the entry count has to match
+        if existing_header[..28] != header[..28] {
+            return None;
+        }
+
+        let count: u32 = u32::from_le_bytes(existing_header[28..32].try_into().ok()?);
+        Some(IdsIndex {
+            storage: IndexStorage::File(file, count)
+        })
+    }
+
+    /// Looks up the string stored under `key`
+    pub fn get(&self, key: u64) -> Option<String> {
+        match &self.storage {
+            IndexStorage::Memory(entries) => {
+                let pos: usize = entries.binary_search_by_key(&key, |x| x.0).ok()?;
+                Some(entries[pos].1.clone())
+            },
+            IndexStorage::File(file, count) => {
+                let mut low: u64 = 0;
+                let mut high: u64 = u64::from(*count);
+                let mut entry: [u8; 16] = [0; 16];
+                while low < high {
+                    let mid: u64 = u64::midpoint(low, high);
+                    file.read_exact_at(&mut entry, HEADER_LEN + mid * ENTRY_LEN).ok()?;
+                    let entry_key: u64 = u64::from_le_bytes(entry[0..8].try_into().ok()?);
+                    match entry_key.cmp(&key) {
+                        Ordering::Less => low = mid + 1,
+                        Ordering::Greater => high = mid,
+                        Ordering::Equal => {
+                            let offset: u64 = u64::from(u32::from_le_bytes(entry[8..12].try_into().ok()?));
+                            let len: u32 = u32::from_le_bytes(entry[12..16].try_into().ok()?);
+                            let mut value: Vec<u8> = vec![0; len as usize];
+                            file.read_exact_at(&mut value, HEADER_LEN + u64::from(*count) * ENTRY_LEN + offset).ok()?;
+                            return String::from_utf8(value).ok();
+                        }
+                    }
+                }
+                None
+            },
+        }
+    }
+
+    /// Vendor name, from pci.ids or usb.ids
+    pub fn vendor(&self, vendor: &str) -> Option<String> {
+        self.get(make_key(vendor, "0", TAG_VENDOR)?)
+    }
+    /// Device name, from pci.ids or usb.ids
+    pub fn device(&self, vendor: &str, device: &str) -> Option<String> {
+        self.get(make_key(vendor, device, TAG_DEVICE)?)
+    }
+    /// Base class name, from pci.ids
+    pub fn class(&self, class: &str) -> Option<String> {
+        self.get(make_key(class, "0", TAG_CLASS)?)
+    }
+    /// Subclass name, from pci.ids
+    pub fn subclass(&self, class: &str, subclass: &str) -> Option<String> {
+        self.get(make_key(class, subclass, TAG_SUBCLASS)?)
+    }
+    /// Marketing name, from amdgpu.ids
+    pub fn amdgpu_model(&self, device: &str, revision: &str) -> Option<String> {
+        self.get(make_key(device, revision, TAG_AMDGPU)?)
+    }
+}
+
+fn make_key(first: &str, second: &str, tag: u64) -> Option<u64> {
+    let first: u64 = u64::from_str_radix(first.trim(), 16).ok()?;
+    let second: u64 = u64::from_str_radix(second.trim(), 16).ok()?;
+    if first > 0xFFFF || second > 0xFFFF {
+        return None;
+    }
+    Some((first << 24) | (second << 8) | tag)
+}
+
+/// `$XDG_CACHE_HOME/CrabFetch`, or `$HOME/.cache/CrabFetch`
+pub fn get_cache_dir() -> Option<PathBuf> {
+    if let Ok(cache_home) = env::var("XDG_CACHE_HOME") {
+        if !cache_home.is_empty() {
+            return Some(PathBuf::from(cache_home).join("CrabFetch"));
+        }
+    }
+    env::var("HOME").ok().map(|x| PathBuf::from(x).join(".cache/CrabFetch"))
+}
+
+fn build_header(metadata: &fs::Metadata, count: u32) -> Header {
+    let mut header: Header = [0; 32];
+    header[0..8].copy_from_slice(INDEX_MAGIC);
+    header[8..16].copy_from_slice(&metadata.size().to_le_bytes());
+    header[16..24].copy_from_slice(&metadata.mtime().to_le_bytes());
+    header[24..28].copy_from_slice(&u32::try_from(metadata.mtime_nsec()).unwrap_or(0).to_le_bytes());
+    header[28..32].copy_from_slice(&count.to_le_bytes());
+    header
+}
+
+fn write_index(index_path: &Path, metadata: &fs::Metadata, entries: &[(u64, String)]) -> Result<(), String> {
+    let count: u32 = u32::try_from(entries.len()).map_err(|e| e.to_string())?;
+    let mut contents: Vec<u8> = build_header(metadata, count).to_vec();
+    let mut strings: Vec<u8> = Vec::new();
+    for (key, value) in entries {
+        let offset: u32 = u32::try_from(strings.len()).map_err(|e| e.to_string())?;
+        let len: u32 = u32::try_from(value.len()).map_err(|e| e.to_string())?;
+        contents.extend_from_slice(&key.to_le_bytes());
+        contents.extend_from_slice(&offset.to_le_bytes());
+        contents.extend_from_slice(&len.to_le_bytes());
+        strings.extend_from_slice(value.as_bytes());
+    }
+    contents.extend_from_slice(&strings);
+
+    if let Some(parent) = index_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    // Written elsewhere and moved in, so that another instance never sees half an index
+    let temp_path: PathBuf = index_path.with_extension(format!("tmp{}", std::process::id()));
+    fs::write(&temp_path, contents).map_err(|e| e.to_string())?;
+    fs::rename(&temp_path, index_path).map_err(|e| e.to_string())
+}
+
+// Sorts by key, keeping the first of any duplicates as that's what a linear scan would've found
+fn finish_entries(mut entries: Vec<(u64, String)>) -> Vec<(u64, String)> {
+    entries.sort_by_key(|x| x.0);
+    entries.dedup_by_key(|x| x.0);
+    entries
+}
+
+// Splits "747e  Navi 32" into it's ID and name
+fn split_id(line: &str, id_len: usize) -> Option<(&str, &str)> {
+    let id: &str = line.get(..id_len)?;
+    if !id.chars().all(|x| x.is_ascii_hexdigit()) {
+        return None;
+    }
+    let name: &str = line.get(id_len..)?;
+    if !name.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some((id, name.trim()))
+}
+
+// vendor
+// 	device
+// 		subvendor subdevice
+// C class
+// 	subclass
+// 		programming interface
+enum Section {
+    Vendor(String),
+    Class(String),
+    Other
+}
+fn parse_hwdata(source: &Path) -> Result<Vec<(u64, String)>, String> {
+    let file: File = match File::open(source) {
+        Ok(r) => r,
+        Err(e) => return Err(format!("Can't read from {} - {e}", source.display())),
+    };
+    let buffer: BufReader<File> = BufReader::new(file);
+
+    let mut section: Section = Section::Other;
+    let mut entries: Vec<(u64, String)> = Vec::new();
+    for line in buffer.lines() {
+        let Ok(line) = line else {
+            continue;
+        };
+        if line.trim().is_empty() || line.trim().starts_with('#') {
+            continue;
+        }
+
+        if let Some(line) = line.strip_prefix('\t') {
+            if line.starts_with('\t') {
+                continue;
+            }
+            match &section {
+                Section::Vendor(vendor) => if let Some((device, name)) = split_id(line, 4) {
+                    if let Some(key) = make_key(vendor, device, TAG_DEVICE) {
+                        entries.push((key, name.to_string()));
+                    }
+                },
+                Section::Class(class) => if let Some((subclass, name)) = split_id(line, 2) {
+                    if let Some(key) = make_key(class, subclass, TAG_SUBCLASS) {
+                        entries.push((key, name.to_string()));
+                    }
+                },
+                Section::Other => {},
+            }
+            continue;
+        }
+
+        if let Some((class, name)) = line.strip_prefix("C ").and_then(|x| split_id(x, 2)) {
+            if let Some(key) = make_key(class, "0", TAG_CLASS) {
+                entries.push((key, name.to_string()));
+            }
+            section = Section::Class(class.to_string());
+        } else if let Some((vendor, name)) = split_id(&line, 4) {
+            if let Some(key) = make_key(vendor, "0", TAG_VENDOR) {
+                entries.push((key, name.to_string()));
+            }
+            section = Section::Vendor(vendor.to_string());
+        } else {
+            // usb.ids has a bunch of other lists at the end, e.g HID usages
+            section = Section::Other;
+        }
+    }
+
+    Ok(finish_entries(entries))
+}
+
+fn parse_amdgpu(source: &Path) -> Result<Vec<(u64, String)>, String> {
+    let file: File = match File::open(source) {
+        Ok(r) => r,
+        Err(e) => return Err(format!("Can't read from {} - {e}", source.display())),
+    };
+    let buffer: BufReader<File> = BufReader::new(file);
+
+    // 747E,	C8,	AMD Radeon RX 7800 XT
+    let mut entries: Vec<(u64, String)> = Vec::new();
+    for line in buffer.lines() {
+        let Ok(line) = line else {
+            continue;
+        };
+        if line.trim().starts_with('#') {
+            continue;
+        }
+
+        let mut values = line.splitn(3, ',');
+        let (Some(device), Some(revision), Some(name)) = (values.next(), values.next(), values.next()) else {
+            continue;
+        };
+        if let Some(key) = make_key(device, revision, TAG_AMDGPU) {
+            entries.push((key, name.trim().to_string()));
+        }
+    }
+
+    Ok(finish_entries(entries))
+}
+
+mod tests {
+    #[test]
+    fn test_ids_index() {
+        use crate::common_sources::ids_index::{IdsFormat, IdsIndex};
+
+        let root: std::path::PathBuf = std::env::temp_dir().join(format!("crabfetch-ids-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        let source: std::path::PathBuf = root.join("pci.ids");
+        let index: std::path::PathBuf = root.join("cache/pci.ids.index");
+        std::fs::write(&source, "# comment\n1002  Advanced Micro Devices, Inc. [AMD/ATI]\n\t747e  Navi 32 [Radeon RX 7700 XT / 7800 XT]\n\t\t1002 0e3b  RX 7800 XT\n10de  NVIDIA Corporation\n\t2203  GA102 [GeForce RTX 3090 Ti]\nC 02  Network controller\n\t00  Ethernet controller\n").unwrap();
+
+        // First run builds it, second reads it back from disk
+        for _ in 0..2 {
+            let ids: IdsIndex = IdsIndex::open_at(&source, Some(&index), IdsFormat::Hwdata).unwrap();
+            assert_eq!(ids.vendor("1002").as_deref(), Some("Advanced Micro Devices, Inc. [AMD/ATI]"));
+            assert_eq!(ids.device("1002", "747E").as_deref(), Some("Navi 32 [Radeon RX 7700 XT / 7800 XT]"));
+            assert_eq!(ids.device("10de", "2203").as_deref(), Some("GA102 [GeForce RTX 3090 Ti]"));
+            assert_eq!(ids.device("10de", "747e"), None);
+            assert_eq!(ids.class("02").as_deref(), Some("Network controller"));
+            assert_eq!(ids.subclass("02", "00").as_deref(), Some("Ethernet controller"));
+            assert!(index.exists());
+        }
+
+        // Changing the source has to rebuild it
+        std::fs::write(&source, "8086  Intel Corporation\n\t5690  DG2 [Arc A770M]\n").unwrap();
+        let ids: IdsIndex = IdsIndex::open_at(&source, Some(&index), IdsFormat::Hwdata).unwrap();
+        assert_eq!(ids.vendor("1002"), None);
+        assert_eq!(ids.device("8086", "5690").as_deref(), Some("DG2 [Arc A770M]"));
+
+        let source: std::path::PathBuf = root.join("amdgpu.ids");
+        std::fs::write(&source, "# comment\n1.0.0\n73FF,\tC1,\tAMD Radeon RX 6600 XT\n747E,\tC8,\tAMD Radeon RX 7800 XT\n").unwrap();
+        let ids: IdsIndex = IdsIndex::open_at(&source, Some(&root.join("cache/amdgpu.ids.index")), IdsFormat::Amdgpu).unwrap();
+        assert_eq!(ids.amdgpu_model("747e", "c8").as_deref(), Some("AMD Radeon RX 7800 XT"));
+        assert_eq!(ids.amdgpu_model("747e", "c1"), None);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,3 +1,11 @@
 pub mod syscalls;
 pub mod package_managers;
 pub mod gtk;
+pub mod smbios;
+pub mod dmi;
+pub mod virtualization;
+pub mod cgroups;
+pub mod pci;
+pub mod hwids;
+pub mod ids_index;
+pub mod wayland_protocols;
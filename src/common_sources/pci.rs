@@ -0,0 +1,116 @@
+// Walks PCI devices through sysfs, used by the GPU and PCI modules
+
+use std::{fs::{self, ReadDir}, path::{Path, PathBuf}};
+
+use crate::util;
+
+pub struct PCIDevice {
+    pub path: PathBuf,
+    pub slot: String,       // 0000:00:1f.3
+    pub vendor_id: String,  // 8086
+    pub device_id: String,
+    pub class: String,      // 04
+    pub subclass: String    // 03
+}
+
+/// Lists every PCI device in /sys/bus/pci/devices
+/// Devices with an unreadable modalias are skipped.
+pub fn list_devices() -> Result<Vec<PCIDevice>, String> {
+    let dir: ReadDir = match fs::read_dir("/sys/bus/pci/devices") {
+        Ok(r) => r,
+        Err(e) => return Err(format!("Can't read from /sys/bus/pci/devices: {e}")),
+    };
+
+    let mut devices: Vec<PCIDevice> = Vec::new();
+    for entry in dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if let Some(device) = read_device(&entry.path()) {
+            devices.push(device);
+        }
+    }
+
+    devices.sort_by(|a, b| a.slot.cmp(&b.slot));
+    Ok(devices)
+}
+
+/// Reads a single PCI device from it's directory in sysfs
+pub fn read_device(path: &Path) -> Option<PCIDevice> {
+    // Credit: https://wiki.archlinux.org/title/Modalias
+    // v - Vendor ID
+    // d - Device ID
+    // sv/sd - Subsys Vendor/Device
+    // bc - Base Class
+    // sc - Sub-class
+    // i - Programming interface
+    // These are all a fixed length so we can just use substringing instead of any fancy parsing
+    // An example: pci:v00001002d0000747Esv00001DA2sd0000D475bc03sc00i00
+    let modalias: String = util::file_read(&path.join("modalias")).ok()?;
+    let modalias: &str = modalias.trim().strip_prefix("pci:")?;
+    if modalias.len() < 46 {
+        return None;
+    }
+
+    Some(PCIDevice {
+        path: path.to_path_buf(),
+        slot: path.file_name()?.to_str()?.to_string(),
+        vendor_id: modalias[5..9].to_lowercase(),
+        device_id: modalias[14..18].to_lowercase(),
+        class: modalias[40..42].to_lowercase(),
+        subclass: modalias[44..46].to_lowercase()
+    })
+}
+
+/// Gives the short name used to filter devices by class, e.g "network"
+// https://github.com/torvalds/linux/blob/master/include/linux/pci_ids.h
+pub fn class_key(class: &str, subclass: &str) -> &'static str {
+    match (class, subclass) {
+        ("01", _) => "storage",
+        ("02", _) => "network",
+        ("03", _) => "display",
+        ("04", "01" | "03") => "audio",
+        ("04", _) => "multimedia",
+        ("05", _) => "memory",
+        ("06", _) => "bridge",
+        ("07", _) => "communication",
+        ("08", _) => "system",
+        ("09", _) => "input",
+        ("0b", _) => "processor",
+        ("0c", "03") => "usb",
+        ("0c", _) => "serial_bus",
+        ("0d", _) => "wireless",
+        ("10", _) => "encryption",
+        ("11", _) => "signal_processing",
+        ("12", _) => "accelerator",
+        _ => "other"
+    }
+}
+
+/// A readable name for a base class, for when pci.ids doesn't know any better
+pub fn class_name(class: &str) -> &'static str {
+    match class {
+        "00" => "Unclassified device",
+        "01" => "Mass storage controller",
+        "02" => "Network controller",
+        "03" => "Display controller",
+        "04" => "Multimedia controller",
+        "05" => "Memory controller",
+        "06" => "Bridge",
+        "07" => "Communication controller",
+        "08" => "Generic system peripheral",
+        "09" => "Input device controller",
+        "0a" => "Docking station",
+        "0b" => "Processor",
+        "0c" => "Serial bus controller",
+        "0d" => "Wireless controller",
+        "0e" => "Intelligent controller",
+        "0f" => "Satellite communications controller",
+        "10" => "Encryption controller",
+        "11" => "Signal processing controller",
+        "12" => "Processing accelerators",
+        "13" => "Non-Essential Instrumentation",
+        "40" => "Coprocessor",
+        _ => "Unknown"
+    }
+}
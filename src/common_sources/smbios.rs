@@ -0,0 +1,108 @@
+// Parses the raw SMBIOS structure table the kernel exposes, for anything /sys/devices/virtual/dmi/id
+// doesn't give us
+// Spec: https://www.dmtf.org/sites/default/files/standards/documents/DSP0134_3.6.0.pdf
+
+use std::fs;
+
+const SMBIOS_TABLE_PATH: &str = "/sys/firmware/dmi/tables/DMI";
+const SMBIOS_TYPE_END_OF_TABLE: u8 = 127;
+
+pub struct SMBIOSStructure<'a> {
+    pub structure_type: u8,
+    pub handle: u16,
+    data: &'a [u8],         // The formatted section, including the header
+    strings: Vec<&'a [u8]>  // The unformatted string-set following it
+}
+impl SMBIOSStructure<'_> {
+    pub fn get_byte(&self, offset: usize) -> Option<u8> {
+        self.data.get(offset).copied()
+    }
+    pub fn get_word(&self, offset: usize) -> Option<u16> {
+        let bytes: &[u8] = self.data.get(offset..offset + 2)?;
+        Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+    pub fn get_dword(&self, offset: usize) -> Option<u32> {
+        let bytes: &[u8] = self.data.get(offset..offset + 4)?;
+        Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+    pub fn get_qword(&self, offset: usize) -> Option<u64> {
+        let bytes: &[u8] = self.data.get(offset..offset + 8)?;
+        let mut buffer: [u8; 8] = [0; 8];
+        buffer.copy_from_slice(bytes);
+        Some(u64::from_le_bytes(buffer))
+    }
+
+    // Strings are referenced by a 1-based index stored at the offset, with 0 meaning no string
+    pub fn get_string(&self, offset: usize) -> Option<String> {
+        let index: usize = usize::from(self.get_byte(offset)?);
+        if index == 0 {
+            return None;
+        }
+
+        let string: String = String::from_utf8_lossy(self.strings.get(index - 1)?).trim().to_string();
+        if string.is_empty() {
+            return None;
+        }
+        Some(string)
+    }
+}
+
+/// Reads the raw SMBIOS table from sysfs
+/// This is only readable by root on most systems.
+pub fn read_table() -> Result<Vec<u8>, String> {
+    match fs::read(SMBIOS_TABLE_PATH) {
+        Ok(r) => Ok(r),
+        Err(e) => Err(format!("Can't read from {SMBIOS_TABLE_PATH} - {e}")),
+    }
+}
+
+/// Splits a raw SMBIOS table into it's structures
+/// Stops at the end of table marker, or at the first structure that would run off the end.
+pub fn parse_table(table: &[u8]) -> Vec<SMBIOSStructure<'_>> {
+    let mut structures: Vec<SMBIOSStructure> = Vec::new();
+
+    let mut position: usize = 0;
+    // 4 bytes is the minimum a header can be
+    while position + 4 <= table.len() {
+        let structure_type: u8 = table[position];
+        let length: usize = usize::from(table[position + 1]);
+        let handle: u16 = u16::from_le_bytes([table[position + 2], table[position + 3]]);
+        if length < 4 || position + length > table.len() {
+            break; // Malformed
+        }
+        let data: &[u8] = &table[position..position + length];
+
+        // The string-set is a list of null terminated strings, ending with a double null
+        let mut strings: Vec<&[u8]> = Vec::new();
+        let mut string_pos: usize = position + length;
+        let mut string_start: usize = string_pos;
+        loop {
+            if string_pos + 1 >= table.len() {
+                return structures; // Ran off the end
+            }
+            if table[string_pos] == 0 {
+                if string_pos != string_start {
+                    strings.push(&table[string_start..string_pos]);
+                }
+                if table[string_pos + 1] == 0 {
+                    break;
+                }
+                string_start = string_pos + 1;
+            }
+            string_pos += 1;
+        }
+        position = string_pos + 2;
+
+        if structure_type == SMBIOS_TYPE_END_OF_TABLE {
+            break;
+        }
+        structures.push(SMBIOSStructure {
+            structure_type,
+            handle,
+            data,
+            strings
+        });
+    }
+
+    structures
+}
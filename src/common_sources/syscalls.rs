@@ -2,7 +2,7 @@
 // This is to prevent duplicate work being done as well as leaving most of our unsafe options in a
 // single place
 
-use std::mem;
+use std::{mem, net::{IpAddr, Ipv4Addr, Ipv6Addr}, ptr};
 
 use libc::{geteuid, getpwuid};
 
@@ -14,6 +14,7 @@ pub struct SyscallCache {
     uname: Option<libc::utsname>,
     euid: Option<u32>,
     passwd: Option<libc::passwd>,
+    ifaddrs: Option<Result<Vec<InterfaceAddress>, String>>,
 }
 impl SyscallCache {
     pub fn new() -> Self {
@@ -22,6 +23,7 @@ impl SyscallCache {
             uname: None,
             euid: None,
             passwd: None,
+            ifaddrs: None,
         }
     }
 
@@ -59,6 +61,49 @@ impl SyscallCache {
         }
         self.passwd = Some(passwd_buffer);
     }
+    fn cache_ifaddrs(&mut self) {
+        // https://man7.org/linux/man-pages/man3/getifaddrs.3.html
+        let mut addresses: Vec<InterfaceAddress> = Vec::new();
+        unsafe {
+            let mut ifaddr: *mut libc::ifaddrs = ptr::null_mut();
+            if libc::getifaddrs(&mut ifaddr) != 0 {
+                self.ifaddrs = Some(Err("getifaddrs syscall failed!".to_string()));
+                return;
+            }
+
+            let mut current: *mut libc::ifaddrs = ifaddr;
+            while !current.is_null() {
+                let ifaddrs: &libc::ifaddrs = &*current;
+                current = ifaddrs.ifa_next;
+
+                // Interfaces without an address still get an entry, with a null address
+                let Some((addr, scope_id)) = sockaddr_to_ip(ifaddrs.ifa_addr) else {
+                    continue;
+                };
+                let Ok(interface) = util::cstr_from_ptr(ifaddrs.ifa_name) else {
+                    continue;
+                };
+                // ifa_ifu is either the broadcast or point-to-point destination address, depending
+                // on the flags
+                let broadcast: Option<IpAddr> = if ifaddrs.ifa_flags & libc::IFF_BROADCAST as u32 != 0 {
+                    sockaddr_to_ip(ifaddrs.ifa_ifu).map(|x| x.0)
+                } else {
+                    None
+                };
+
+                addresses.push(InterfaceAddress {
+                    interface,
+                    addr,
+                    netmask: sockaddr_to_ip(ifaddrs.ifa_netmask).map(|x| x.0),
+                    broadcast,
+                    scope_id
+                });
+            }
+
+            libc::freeifaddrs(ifaddr);
+        }
+        self.ifaddrs = Some(Ok(addresses));
+    }
 
     // Get the syscalls, and process/cache them if they're not gotten already
     pub fn get_sysinfo_cached(&mut self) -> libc::sysinfo {
@@ -88,6 +133,33 @@ impl SyscallCache {
 
         Passwd::from_libc(self.passwd.unwrap())
     }
+    pub fn get_ifaddrs_cached(&mut self) -> Result<Vec<InterfaceAddress>, String> {
+        if self.ifaddrs.is_none() {
+            self.cache_ifaddrs();
+        }
+
+        self.ifaddrs.clone().unwrap()
+    }
+}
+
+// Gives back the IP, and the scope ID for IPv6 addresses
+#[allow(clippy::cast_ptr_alignment)] // the family tells us what it actually is
+unsafe fn sockaddr_to_ip(addr: *const libc::sockaddr) -> Option<(IpAddr, u32)> {
+    if addr.is_null() {
+        return None;
+    }
+
+    match i32::from((*addr).sa_family) {
+        libc::AF_INET => {
+            let addr: *const libc::sockaddr_in = addr.cast::<libc::sockaddr_in>();
+            Some((IpAddr::V4(Ipv4Addr::from((*addr).sin_addr.s_addr.to_be())), 0))
+        },
+        libc::AF_INET6 => {
+            let addr: *const libc::sockaddr_in6 = addr.cast::<libc::sockaddr_in6>();
+            Some((IpAddr::V6(Ipv6Addr::from((*addr).sin6_addr.s6_addr)), (*addr).sin6_scope_id))
+        },
+        _ => None
+    }
 }
 
 // Better syscall structures than the built in libc ones
@@ -115,6 +187,28 @@ impl Utsname {
 }
 
 
+#[allow(dead_code)]
+#[derive(Clone)]
+pub struct InterfaceAddress {
+    pub interface: String,
+    pub addr: IpAddr,
+    pub netmask: Option<IpAddr>,
+    pub broadcast: Option<IpAddr>,
+    // https://man7.org/linux/man-pages/man7/ipv6.7.html
+    // Non-zero for link-local IPv6 addresses, always 0 for IPv4
+    pub scope_id: u32
+}
+impl InterfaceAddress {
+    // Counts the bits set in the netmask, e.g 255.255.255.0 -> 24
+    pub fn prefix_length(&self) -> Option<u32> {
+        match self.netmask? {
+            IpAddr::V4(r) => Some(u32::from(r).count_ones()),
+            IpAddr::V6(r) => Some(u128::from(r).count_ones()),
+        }
+    }
+}
+
+
 #[allow(dead_code)]
 pub struct Passwd {
     pub name: String,
@@ -0,0 +1,165 @@
+// Detects if we're running inside of a VM or container
+// Shared between the virtualization module and host's placeholders
+
+use std::path::Path;
+
+#[cfg(target_arch = "x86_64")]
+use raw_cpuid::{CpuId, Hypervisor};
+
+use crate::util;
+
+// DMI values a hypervisor may give, and what to call it
+// Checked against sys_vendor, product_name, board_vendor and bios_vendor in that order
+const DMI_VENDORS: &[(&str, &str)] = &[
+    ("KVM", "KVM"),
+    ("OpenStack", "KVM"),
+    ("KubeVirt", "KVM"),
+    ("Amazon EC2", "Amazon EC2"),
+    ("Google Compute Engine", "Google Compute Engine"),
+    ("QEMU", "QEMU"),
+    ("VMware", "VMware"),
+    ("VMW", "VMware"),
+    ("innotek GmbH", "VirtualBox"),
+    ("VirtualBox", "VirtualBox"),
+    ("Oracle Corporation", "VirtualBox"),
+    ("Xen", "Xen"),
+    ("Bochs", "Bochs"),
+    ("Parallels", "Parallels"),
+    ("BHYVE", "bhyve"),
+    ("Hyper-V", "Hyper-V"),
+    ("Apple Virtualization", "Apple Virtualization"),
+];
+const DMI_FILES: &[&str] = &[
+    "sys_vendor",
+    "product_name",
+    "board_vendor",
+    "bios_vendor"
+];
+
+/// Returns the name of the hypervisor we're running under, if any
+pub fn detect_hypervisor() -> Option<String> {
+    // DMI first, as it tends to be more specific than CPUID
+    // e.g VirtualBox will report as KVM through CPUID when using it's paravirtualization
+    for file in DMI_FILES {
+        let Ok(value) = util::file_read(&Path::new("/sys/devices/virtual/dmi/id").join(file)) else {
+            continue;
+        };
+        let value: &str = value.trim();
+        if let Some((_, name)) = DMI_VENDORS.iter().find(|(x, _)| value.starts_with(x)) {
+            return Some((*name).to_string());
+        }
+        // Hyper-V doesn't identify itself beyond this
+        if value == "Microsoft Corporation" && util::file_read(Path::new("/sys/devices/virtual/dmi/id/product_name")).is_ok_and(|x| x.trim() == "Virtual Machine") {
+            return Some("Hyper-V".to_string());
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    if let Some(hypervisor) = detect_cpuid_hypervisor() {
+        return Some(hypervisor);
+    }
+
+    // Xen PV guests won't show up in either of the above
+    // dom0 also has /proc/xen, but is the host so shouldn't count
+    if Path::new("/proc/xen").exists() && !util::file_read(Path::new("/proc/xen/capabilities")).is_ok_and(|x| x.contains("control_d")) {
+        return Some("Xen".to_string());
+    }
+    if let Ok(hypervisor) = util::file_read(Path::new("/sys/hypervisor/type")) {
+        let hypervisor: &str = hypervisor.trim();
+        if !hypervisor.is_empty() {
+            return Some(match hypervisor {
+                "xen" => "Xen".to_string(),
+                _ => hypervisor.to_string()
+            });
+        }
+    }
+
+    None
+}
+
+// CPUID's hypervisor bit, and the vendor leaf
+#[cfg(target_arch = "x86_64")]
+fn detect_cpuid_hypervisor() -> Option<String> {
+    let cpuid = CpuId::new();
+    if !cpuid.get_feature_info().is_some_and(|x| x.has_hypervisor()) {
+        return None;
+    }
+
+    let Some(info) = cpuid.get_hypervisor_info() else {
+        return Some("Unknown".to_string());
+    };
+    let name: &str = match info.identify() {
+        Hypervisor::Xen => "Xen",
+        Hypervisor::VMware => "VMware",
+        Hypervisor::HyperV => "Hyper-V",
+        Hypervisor::KVM => "KVM",
+        Hypervisor::QEMU => "QEMU",
+        Hypervisor::Bhyve => "bhyve",
+        Hypervisor::QNX => "QNX",
+        Hypervisor::ACRN => "ACRN",
+        Hypervisor::Unknown(..) => "Unknown",
+    };
+    Some(name.to_string())
+}
+
+/// Returns the name of the container we're running inside of, if any
+pub fn detect_container() -> Option<String> {
+    if util::in_wsl() {
+        return Some("WSL".to_string());
+    }
+    if Path::new("/.dockerenv").exists() {
+        return Some("Docker".to_string());
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Some("Podman".to_string());
+    }
+
+    // systemd and most container managers set container= on PID 1
+    // This is usually root only to read, but systemd also leaves a copy in /run
+    let mut container: Option<String> = util::file_read(Path::new("/run/systemd/container")).ok()
+        .map(|x| x.trim().to_string());
+    if container.is_none() {
+        if let Ok(environ) = util::file_read(Path::new("/proc/1/environ")) {
+            container = environ.split('\0')
+                .find_map(|x| x.strip_prefix("container="))
+                .map(ToString::to_string);
+        }
+    }
+    if let Some(container) = container.filter(|x| !x.is_empty()) {
+        return Some(container_name(&container));
+    }
+
+    // Lastly, the cgroup we're in may be named after the container manager
+    if let Ok(cgroup) = util::file_read(Path::new("/proc/1/cgroup")) {
+        if cgroup.contains("kubepods") {
+            return Some("Kubernetes".to_string());
+        }
+        if cgroup.contains("/docker") {
+            return Some("Docker".to_string());
+        }
+        if cgroup.contains("/libpod") {
+            return Some("Podman".to_string());
+        }
+        if cgroup.contains("/lxc") {
+            return Some("LXC".to_string());
+        }
+    }
+
+    None
+}
+
+fn container_name(id: &str) -> String {
+    match id {
+        "docker" => "Docker",
+        "podman" => "Podman",
+        "lxc" => "LXC",
+        "lxc-libvirt" => "LXC (libvirt)",
+        "systemd-nspawn" => "systemd-nspawn",
+        "oci" => "OCI",
+        "wsl" => "WSL",
+        "proot" => "proot",
+        "pouch" => "Pouch",
+        "rkt" => "rkt",
+        _ => id
+    }.to_string()
+}
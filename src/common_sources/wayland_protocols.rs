@@ -0,0 +1,32 @@
+// Bindings for the Wayland protocols the displays module uses beyond the core ones
+// wayland-client only ships with the core protocol, so these are generated from copies of the
+// protocol files in protocols/
+#![allow(clippy::all, clippy::pedantic, dead_code, unused_imports, non_upper_case_globals)]
+
+pub mod xdg_output {
+    use wayland_client;
+    use wayland_client::protocol::*;
+
+    pub mod __interfaces {
+        use wayland_client::backend as wayland_backend;
+        use wayland_client::protocol::__interfaces::*;
+        wayland_scanner::generate_interfaces!("protocols/xdg-output-unstable-v1.xml");
+    }
+    use self::__interfaces::*;
+
+    wayland_scanner::generate_client_code!("protocols/xdg-output-unstable-v1.xml");
+}
+
+pub mod wlr_output_management {
+    use wayland_client;
+    use wayland_client::protocol::*;
+
+    pub mod __interfaces {
+        use wayland_client::backend as wayland_backend;
+        use wayland_client::protocol::__interfaces::*;
+        wayland_scanner::generate_interfaces!("protocols/wlr-output-management-unstable-v1.xml");
+    }
+    use self::__interfaces::*;
+
+    wayland_scanner::generate_client_code!("protocols/wlr-output-management-unstable-v1.xml");
+}
@@ -3,7 +3,7 @@ use std::{env, fmt::{Debug, Display}, fs::{self, File}, io::Write, path::{Path,
 use config::{builder::DefaultState, Config, ConfigBuilder};
 use serde::Deserialize;
 
-use crate::{ascii::AsciiConfiguration, battery::BatteryConfiguration, cpu::CPUConfiguration, datetime::DateTimeConfiguration, desktop::DesktopConfiguration, displays::DisplayConfiguration, editor::EditorConfiguration, formatter::CrabFetchColor, gpu::GPUConfiguration, host::HostConfiguration, hostname::HostnameConfiguration, initsys::InitSystemConfiguration, locale::LocaleConfiguration, memory::MemoryConfiguration, modules::{icon_theme::IconThemeConfiguration, localip::LocalIPConfiguration, theme::ThemeConfiguration}, mounts::MountConfiguration, os::OSConfiguration, packages::PackagesConfiguration, preset_configs, processes::ProcessesConfiguration, shell::ShellConfiguration, swap::SwapConfiguration, terminal::TerminalConfiguration, uptime::UptimeConfiguration, util};
+use crate::{ascii::AsciiConfiguration, audio::AudioConfiguration, battery::BatteryConfiguration, brightness::BrightnessConfiguration, cpu::CPUConfiguration, datetime::DateTimeConfiguration, desktop::DesktopConfiguration, disks::DiskConfiguration, diskio::DiskIOConfiguration, displays::DisplayConfiguration, editor::EditorConfiguration, firmware::FirmwareConfiguration, formatter::CrabFetchColor, gpu::GPUConfiguration, host::HostConfiguration, hostname::HostnameConfiguration, initsys::InitSystemConfiguration, locale::LocaleConfiguration, memory::MemoryConfiguration, motherboard::MotherboardConfiguration, modules::{icon_theme::IconThemeConfiguration, localip::LocalIPConfiguration, theme::ThemeConfiguration}, mounts::MountConfiguration, network::NetworkConfiguration, netspeed::NetSpeedConfiguration, os::OSConfiguration, packages::PackagesConfiguration, pci::PCIConfiguration, preset_configs, processes::ProcessesConfiguration, security_cpu::SecurityCPUConfiguration, shell::ShellConfiguration, swap::SwapConfiguration, terminal::TerminalConfiguration, uptime::UptimeConfiguration, usb::USBConfiguration, util, virtualization::VirtualizationConfiguration};
 #[cfg(feature = "player")]
 use crate::player::PlayerConfiguration;
 
@@ -35,6 +35,7 @@ pub struct Configuration {
     pub percentage_color_thresholds: Vec<String>,
     pub use_ibis: bool,
     pub suppress_errors: bool,
+    pub pci_ids_path: Option<String>,
 
     pub ascii: AsciiConfiguration,
 
@@ -44,6 +45,13 @@ pub struct Configuration {
     pub memory: MemoryConfiguration,
     pub swap: SwapConfiguration,
     pub mounts: MountConfiguration,
+    pub disks: DiskConfiguration,
+    pub diskio: DiskIOConfiguration,
+    pub pci: PCIConfiguration,
+    pub usb: USBConfiguration,
+    pub firmware: FirmwareConfiguration,
+    pub motherboard: MotherboardConfiguration,
+    pub virtualization: VirtualizationConfiguration,
     pub host: HostConfiguration,
     pub displays: DisplayConfiguration,
     pub os: OSConfiguration,
@@ -52,7 +60,9 @@ pub struct Configuration {
     pub terminal: TerminalConfiguration,
     pub shell: ShellConfiguration,
     pub uptime: UptimeConfiguration,
+    pub audio: AudioConfiguration,
     pub battery: BatteryConfiguration,
+    pub brightness: BrightnessConfiguration,
     pub locale: LocaleConfiguration,
     #[cfg(feature = "player")]
     pub player: PlayerConfiguration,
@@ -61,8 +71,11 @@ pub struct Configuration {
     pub processes: ProcessesConfiguration,
     pub datetime: DateTimeConfiguration,
     pub localip: LocalIPConfiguration,
+    pub network: NetworkConfiguration,
+    pub netspeed: NetSpeedConfiguration,
     pub theme: ThemeConfiguration,
-    pub icontheme: IconThemeConfiguration
+    pub icontheme: IconThemeConfiguration,
+    pub security_cpu: SecurityCPUConfiguration
 }
 
 // Config Error 
@@ -374,6 +387,7 @@ pub fn fill_builder_defaults(mut builder: ConfigBuilder<DefaultState>) -> Config
     builder = builder.set_default("cpu.title", "CPU").unwrap();
     builder = builder.set_default("cpu.format", "{name} ({core_count}c {thread_count}t) @ {max_clock_ghz} GHz").unwrap();
     builder = builder.set_default("cpu.remove_trailing_processor", true).unwrap();
+    builder = builder.set_default("cpu.use_cgroup_limits", false).unwrap();
 
     builder = builder.set_default("gpu.amd_accuracy", true).unwrap();
     builder = builder.set_default("gpu.ignore_disabled_gpus", true).unwrap();
@@ -383,13 +397,58 @@ pub fn fill_builder_defaults(mut builder: ConfigBuilder<DefaultState>) -> Config
 
     builder = builder.set_default("memory.title", "Memory").unwrap();
     builder = builder.set_default("memory.format", "{used} / {max} ({percent})").unwrap();
+    builder = builder.set_default("memory.used_definition", "available").unwrap();
+    builder = builder.set_default("memory.use_cgroup_limits", false).unwrap();
+    builder = builder.set_default("memory.newline_dimms", false).unwrap();
+    builder = builder.set_default("memory.dimm_title", "Memory ({locator})").unwrap();
+    builder = builder.set_default("memory.dimm_format", "{manufacturer} {size} {type} @ {speed} MT/s").unwrap();
 
     builder = builder.set_default("swap.title", "Swap").unwrap();
     builder = builder.set_default("swap.format", "{used} / {total} ({percent})").unwrap();
+    builder = builder.set_default("swap.list_devices", false).unwrap();
+    builder = builder.set_default("swap.device_title", "Swap ({name})").unwrap();
+    builder = builder.set_default("swap.device_format", "{used} / {total} ({percent}) [{type}]").unwrap();
 
     builder = builder.set_default("mounts.title", "Disk ({mount})").unwrap();
     builder = builder.set_default("mounts.format", "{space_used} used of {space_total} ({percent}) [{filesystem}]").unwrap();
     builder = builder.set_default("mounts.ignore", vec![""]).unwrap();
+    builder = builder.set_default("mounts.include_filesystems", Vec::<String>::new()).unwrap();
+    builder = builder.set_default("mounts.exclude_filesystems", Vec::<String>::new()).unwrap();
+    builder = builder.set_default("mounts.include_devices", Vec::<String>::new()).unwrap();
+    builder = builder.set_default("mounts.exclude_devices", Vec::<String>::new()).unwrap();
+    builder = builder.set_default("mounts.show_network", false).unwrap();
+    builder = builder.set_default("mounts.show_virtual", false).unwrap();
+    builder = builder.set_default("mounts.sort_by", "none").unwrap();
+    builder = builder.set_default("mounts.df_compatible", false).unwrap();
+    builder = builder.set_default("mounts.deduplicate", true).unwrap();
+
+    builder = builder.set_default("disks.title", "Drive ({name})").unwrap();
+    builder = builder.set_default("disks.format", "{model} ({size}) [{type}]").unwrap();
+    builder = builder.set_default("disks.show_serial", false).unwrap();
+    builder = builder.set_default("disks.ignore", Vec::<String>::new()).unwrap();
+
+    builder = builder.set_default("diskio.title", "Disk IO ({name})").unwrap();
+    builder = builder.set_default("diskio.format", "{read}/s read, {write}/s write ({percent} busy)").unwrap();
+    builder = builder.set_default("diskio.interval_ms", 250).unwrap();
+    builder = builder.set_default("diskio.ignore", Vec::<String>::new()).unwrap();
+
+    builder = builder.set_default("pci.title", "{class}").unwrap();
+    builder = builder.set_default("pci.format", "{vendor} {device}").unwrap();
+    builder = builder.set_default("pci.include", vec!["storage", "network", "audio", "usb"]).unwrap();
+    builder = builder.set_default("pci.exclude", Vec::<String>::new()).unwrap();
+
+    builder = builder.set_default("usb.title", "USB ({port})").unwrap();
+    builder = builder.set_default("usb.format", "{vendor} {product}").unwrap();
+    builder = builder.set_default("usb.show_hubs", false).unwrap();
+
+    builder = builder.set_default("firmware.title", "Firmware").unwrap();
+    builder = builder.set_default("firmware.format", "{vendor} {version} ({date}) [{boot_mode}]").unwrap();
+
+    builder = builder.set_default("motherboard.title", "Motherboard").unwrap();
+    builder = builder.set_default("motherboard.format", "{vendor} {name}").unwrap();
+
+    builder = builder.set_default("virtualization.title", "Virtualization").unwrap();
+    builder = builder.set_default("virtualization.format", "{virtualization}").unwrap();
 
     builder = builder.set_default("host.title", "Host").unwrap();
     builder = builder.set_default("host.format", "{host} ({chassis})").unwrap();
@@ -400,6 +459,7 @@ pub fn fill_builder_defaults(mut builder: ConfigBuilder<DefaultState>) -> Config
     builder = builder.set_default("displays.title", "Display ({make} {model})").unwrap();
     builder = builder.set_default("displays.format", "{width}x{height} @ {refresh_rate}Hz ({name})").unwrap();
     builder = builder.set_default("displays.scale_size", false).unwrap();
+    builder = builder.set_default("displays.show_disabled", false).unwrap();
 
     builder = builder.set_default("os.title", "Operating System").unwrap();
     builder = builder.set_default("os.format", "{distro} ({kernel})").unwrap();
@@ -427,6 +487,17 @@ pub fn fill_builder_defaults(mut builder: ConfigBuilder<DefaultState>) -> Config
 
     builder = builder.set_default("battery.title", "Battery {index}").unwrap();
     builder = builder.set_default("battery.format", "{percentage}%").unwrap();
+    builder = builder.set_default("battery.devices", "system").unwrap();
+
+    builder = builder.set_default("brightness.title", "Brightness ({name})").unwrap();
+    builder = builder.set_default("brightness.format", "{percent}").unwrap();
+    builder = builder.set_default("brightness.show_keyboard", true).unwrap();
+
+    builder = builder.set_default("audio.title", "Audio").unwrap();
+    builder = builder.set_default("audio.format", "{server} {server_version}").unwrap();
+    builder = builder.set_default("audio.list_cards", false).unwrap();
+    builder = builder.set_default("audio.card_title", "Sound Card {index}").unwrap();
+    builder = builder.set_default("audio.card_format", "{name} [{driver}]").unwrap();
 
     builder = builder.set_default("editor.title", "Editor").unwrap();
     builder = builder.set_default("editor.format", "{name} {version}").unwrap();
@@ -449,6 +520,22 @@ pub fn fill_builder_defaults(mut builder: ConfigBuilder<DefaultState>) -> Config
 
     builder = builder.set_default("localip.title", "Local IP ({interface})").unwrap();
     builder = builder.set_default("localip.format", "{addr}").unwrap();
+    builder = builder.set_default("localip.address_family", "both").unwrap();
+    builder = builder.set_default("localip.include_virtual", Vec::<String>::new()).unwrap();
+    builder = builder.set_default("localip.exclude_scopes", vec!["link-local"]).unwrap();
+    builder = builder.set_default("localip.collapse", false).unwrap();
+
+    builder = builder.set_default("network.title", "Network ({name})").unwrap();
+    builder = builder.set_default("network.format", "{addresses} ({type}, {state})").unwrap();
+    builder = builder.set_default("network.show_mac", false).unwrap();
+    builder = builder.set_default("network.include", Vec::<String>::new()).unwrap();
+    builder = builder.set_default("network.exclude", vec!["loopback"]).unwrap();
+
+    builder = builder.set_default("netspeed.title", "Net Speed ({name})").unwrap();
+    builder = builder.set_default("netspeed.format", "{rx}/s down, {tx}/s up").unwrap();
+    builder = builder.set_default("netspeed.interval_ms", 250).unwrap();
+    builder = builder.set_default("netspeed.include", Vec::<String>::new()).unwrap();
+    builder = builder.set_default("netspeed.exclude", vec!["loopback"]).unwrap();
 
     builder = builder.set_default("theme.title", "Theme").unwrap();
     builder = builder.set_default("theme.format", "Gtk3: {gtk3}  Gtk4: {gtk4}").unwrap();
@@ -456,5 +543,13 @@ pub fn fill_builder_defaults(mut builder: ConfigBuilder<DefaultState>) -> Config
     builder = builder.set_default("icontheme.title", "Icons").unwrap();
     builder = builder.set_default("icontheme.format", "Gtk3: {gtk3}  Gtk4: {gtk4}").unwrap();
 
+    builder = builder.set_default("security_cpu.title", "CPU Vulnerabilities").unwrap();
+    builder = builder.set_default("security_cpu.format", "{vulnerable} vulnerable, {mitigated} mitigated").unwrap();
+    builder = builder.set_default("security_cpu.list_entries", false).unwrap();
+    builder = builder.set_default("security_cpu.entry_title", "{name}").unwrap();
+    builder = builder.set_default("security_cpu.entry_format", "{status}").unwrap();
+    builder = builder.set_default("security_cpu.vulnerable_format", "{color-brightred}{status}").unwrap();
+    builder = builder.set_default("security_cpu.ignore", Vec::<String>::new()).unwrap();
+
     builder
 }
@@ -144,7 +144,11 @@ pub fn process_percentage_placeholder(text: &str, percentage: f32, config: &Conf
 
 #[allow(clippy::cast_precision_loss)]
 pub fn auto_format_bytes(kilobytes: u64, ibis: bool, dec_places: u32) -> String {
-    let mut result: f64 = kilobytes as f64;
+    auto_format_bytes_f64(kilobytes as f64, ibis, dec_places)
+}
+// For values that can be fractions of a kilobyte, like rates
+pub fn auto_format_bytes_f64(kilobytes: f64, ibis: bool, dec_places: u32) -> String {
+    let mut result: f64 = kilobytes;
     let mut steps: u8 = 0; // 0 - Kilo, 1 - Mega, 2 - Giga, 3 - Tera 
     let divider = if ibis {1024} else {1000};
     if ibis {
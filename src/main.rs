@@ -10,12 +10,23 @@ use ascii::AsciiMode;
 use common_sources::gtk::GTKSettingsCache;
 use formatter::CrabFetchColor;
 use module::{Module, ModuleError};
+use modules::audio::{self, AudioInfo};
 use modules::battery::{self, BatteryInfo};
+use modules::brightness::{self, BrightnessInfo};
 use modules::cpu::{self, CPUInfo};
 use clap::{ArgAction, Parser};
 use colored::Colorize;
 use modules::datetime::{self, DateTimeInfo};
 use modules::desktop::{self, DesktopInfo};
+use modules::disks::{self, DiskInfo};
+use modules::pci::{self, PCIInfo};
+use modules::usb::{self, USBInfo};
+use modules::diskio::{self, DiskIOInfo};
+use modules::firmware::{self, FirmwareInfo};
+use modules::motherboard::{self, MotherboardInfo};
+use modules::virtualization::{self, VirtualizationInfo};
+use modules::network::{self, NetworkInfo};
+use modules::netspeed::{self, NetSpeedInfo};
 use modules::displays::{self, DisplayInfo};
 use modules::editor::{self, EditorInfo};
 use modules::host::{self, HostInfo};
@@ -30,6 +41,7 @@ use modules::player::{self, PlayerInfo};
 use modules::os::{self, OSInfo};
 use modules::packages::{self, PackagesInfo};
 use modules::processes::{self, ProcessesInfo};
+use modules::security_cpu::{self, SecurityCPUInfo};
 use modules::shell::{self, ShellInfo};
 use modules::swap::{self, SwapInfo};
 use modules::terminal::{self, TerminalInfo};
@@ -193,7 +205,9 @@ struct ModuleOutputs {
     desktop: Option<Result<DesktopInfo, ModuleError>>,
     terminal: Option<Result<TerminalInfo, ModuleError>>,
     shell: Option<Result<ShellInfo, ModuleError>>,
+    audio: Option<Result<AudioInfo, ModuleError>>,
     battery: Option<Result<Vec<BatteryInfo>, ModuleError>>,
+    brightness: Option<Result<Vec<BrightnessInfo>, ModuleError>>,
     uptime: Option<Result<UptimeInfo, ModuleError>>,
     locale: Option<Result<LocaleInfo, ModuleError>>,
     #[cfg(feature = "player")]
@@ -206,6 +220,16 @@ struct ModuleOutputs {
     localip: Option<Result<Vec<LocalIPInfo>, ModuleError>>,
     theme: Option<Result<ThemeInfo, ModuleError>>,
     icon_theme: Option<Result<IconThemeInfo, ModuleError>>,
+    security_cpu: Option<Result<SecurityCPUInfo, ModuleError>>,
+    disks: Option<Result<Vec<DiskInfo>, ModuleError>>,
+    diskio: Option<Result<Vec<DiskIOInfo>, ModuleError>>,
+    pci: Option<Result<Vec<PCIInfo>, ModuleError>>,
+    usb: Option<Result<Vec<USBInfo>, ModuleError>>,
+    firmware: Option<Result<FirmwareInfo, ModuleError>>,
+    motherboard: Option<Result<MotherboardInfo, ModuleError>>,
+    virtualization: Option<Result<VirtualizationInfo, ModuleError>>,
+    network: Option<Result<Vec<NetworkInfo>, ModuleError>>,
+    netspeed: Option<Result<Vec<NetSpeedInfo>, ModuleError>>,
 }
 impl ModuleOutputs {
     fn new() -> Self {
@@ -222,7 +246,9 @@ impl ModuleOutputs {
             desktop: None,
             terminal: None,
             shell: None,
+            audio: None,
             battery: None,
+            brightness: None,
             uptime: None,
             locale: None,
             #[cfg(feature = "player")]
@@ -235,6 +261,16 @@ impl ModuleOutputs {
             processes: None,
             datetime: None,
             localip: None,
+            security_cpu: None,
+            disks: None,
+            diskio: None,
+            pci: None,
+            usb: None,
+            firmware: None,
+            motherboard: None,
+            virtualization: None,
+            network: None,
+            netspeed: None,
         }
     }
 }
@@ -431,12 +467,47 @@ fn main() {
             },
             "memory" => {
                 let bench: Option<Instant> = benchmark_point(args.benchmark); 
-                run_generic_module!(memory, MemoryInfo, get_memory, known_outputs.memory, config, log_errors, output, );
+                if known_outputs.memory.is_none() {
+                    known_outputs.memory = Some(memory::get_memory(&config));
+                }
+                match known_outputs.memory.as_ref().unwrap() {
+                    Ok(memory) => {
+                        output.push(memory.style(&config));
+                        if config.memory.newline_dimms {
+                            output.extend(memory.style_dimms(&config));
+                        }
+                    },
+                    Err(e) => {
+                        if log_errors {
+                            output.push((String::new(), e.to_string()));
+                        } else {
+                            output.push(MemoryInfo::unknown_output(&config));
+                        }
+                    },
+                }
                 print_bench_time(args.benchmark, args.benchmark_warn, "Memory Module", bench);
             },
             "swap" => {
                 let bench: Option<Instant> = benchmark_point(args.benchmark); 
-                run_generic_module!(swap, SwapInfo, get_swap, known_outputs.swap, config, log_errors, output, &mut syscall_cache);
+                if known_outputs.swap.is_none() {
+                    known_outputs.swap = Some(swap::get_swap(&config, &mut syscall_cache));
+                }
+                match known_outputs.swap.as_ref().unwrap() {
+                    Ok(swap) => {
+                        if config.swap.list_devices {
+                            output.append(&mut swap.style_devices(&config));
+                        } else {
+                            output.push(swap.style(&config));
+                        }
+                    },
+                    Err(e) => {
+                        if log_errors {
+                            output.push((String::new(), e.to_string()));
+                        } else {
+                            output.push(SwapInfo::unknown_output(&config));
+                        }
+                    },
+                }
                 print_bench_time(args.benchmark, args.benchmark_warn, "Swap Module", bench);
             },
             "mounts" => {
@@ -463,6 +534,26 @@ fn main() {
                 }; 
                 print_bench_time(args.benchmark, args.benchmark_warn, "Mounts Module", bench);
             },
+            "disks" => {
+                let bench: Option<Instant> = benchmark_point(args.benchmark); 
+                run_multiline_module!(disks, DiskInfo, get_disks, known_outputs.disks, config, log_errors, output, &config);
+                print_bench_time(args.benchmark, args.benchmark_warn, "Disks Module", bench);
+            },
+            "diskio" => {
+                let bench: Option<Instant> = benchmark_point(args.benchmark); 
+                run_multiline_module!(diskio, DiskIOInfo, get_disk_io, known_outputs.diskio, config, log_errors, output, &config);
+                print_bench_time(args.benchmark, args.benchmark_warn, "Disk IO Module", bench);
+            },
+            "pci" => {
+                let bench: Option<Instant> = benchmark_point(args.benchmark); 
+                run_multiline_module!(pci, PCIInfo, get_pci_devices, known_outputs.pci, config, log_errors, output, &config);
+                print_bench_time(args.benchmark, args.benchmark_warn, "PCI Module", bench);
+            },
+            "usb" => {
+                let bench: Option<Instant> = benchmark_point(args.benchmark); 
+                run_multiline_module!(usb, USBInfo, get_usb_devices, known_outputs.usb, config, log_errors, output, &config);
+                print_bench_time(args.benchmark, args.benchmark_warn, "USB Module", bench);
+            },
             "host" => {
                 let bench: Option<Instant> = benchmark_point(args.benchmark); 
                 if known_outputs.host.is_none() {
@@ -485,6 +576,21 @@ fn main() {
                 }; 
                 print_bench_time(args.benchmark, args.benchmark_warn, "Host Module", bench);
             },
+            "firmware" => {
+                let bench: Option<Instant> = benchmark_point(args.benchmark); 
+                run_generic_module!(firmware, FirmwareInfo, get_firmware, known_outputs.firmware, config, log_errors, output, &config);
+                print_bench_time(args.benchmark, args.benchmark_warn, "Firmware Module", bench);
+            },
+            "motherboard" => {
+                let bench: Option<Instant> = benchmark_point(args.benchmark); 
+                run_generic_module!(motherboard, MotherboardInfo, get_motherboard, known_outputs.motherboard, config, log_errors, output, &config);
+                print_bench_time(args.benchmark, args.benchmark_warn, "Motherboard Module", bench);
+            },
+            "virtualization" => {
+                let bench: Option<Instant> = benchmark_point(args.benchmark); 
+                run_generic_module!(virtualization, VirtualizationInfo, get_virtualization, known_outputs.virtualization, config, log_errors, output, &config);
+                print_bench_time(args.benchmark, args.benchmark_warn, "Virtualization Module", bench);
+            },
             "displays" => {
                 let bench: Option<Instant> = benchmark_point(args.benchmark); 
                 run_multiline_module!(displays, DisplayInfo, get_displays, known_outputs.displays, config, log_errors, output, &config);
@@ -537,9 +643,37 @@ fn main() {
             },
             "battery" => {
                 let bench: Option<Instant> = benchmark_point(args.benchmark); 
-                run_multiline_module!(battery, BatteryInfo, get_batteries, known_outputs.battery, config, log_errors, output, );
+                run_multiline_module!(battery, BatteryInfo, get_batteries, known_outputs.battery, config, log_errors, output, &config);
                 print_bench_time(args.benchmark, args.benchmark_warn, "Battery Module", bench);
             },
+            "brightness" => {
+                let bench: Option<Instant> = benchmark_point(args.benchmark); 
+                run_multiline_module!(brightness, BrightnessInfo, get_brightness, known_outputs.brightness, config, log_errors, output, &config);
+                print_bench_time(args.benchmark, args.benchmark_warn, "Brightness Module", bench);
+            },
+            "audio" => {
+                let bench: Option<Instant> = benchmark_point(args.benchmark); 
+                if known_outputs.audio.is_none() {
+                    known_outputs.audio = Some(audio::get_audio(&config, &package_managers));
+                }
+                match known_outputs.audio.as_ref().unwrap() {
+                    Ok(audio) => {
+                        if config.audio.list_cards {
+                            output.append(&mut audio.style_cards(&config));
+                        } else {
+                            output.push(audio.style(&config));
+                        }
+                    },
+                    Err(e) => {
+                        if log_errors {
+                            output.push((String::new(), e.to_string()));
+                        } else {
+                            output.push(AudioInfo::unknown_output(&config));
+                        }
+                    },
+                }
+                print_bench_time(args.benchmark, args.benchmark_warn, "Audio Module", bench);
+            },
             "uptime" => {
                 let bench: Option<Instant> = benchmark_point(args.benchmark); 
                 run_generic_module!(uptime, UptimeInfo, get_uptime, known_outputs.uptime, config, log_errors, output, &mut syscall_cache);
@@ -581,7 +715,7 @@ fn main() {
             },
             "localip" => {
                 let bench: Option<Instant> = benchmark_point(args.benchmark); 
-                run_multiline_module!(localip, LocalIPInfo, get_local_ips, known_outputs.localip, config, log_errors, output, );
+                run_multiline_module!(localip, LocalIPInfo, get_local_ips, known_outputs.localip, config, log_errors, output, &config, &mut syscall_cache);
                 print_bench_time(args.benchmark, args.benchmark_warn, "Local IP Module", bench);
             }
             "theme" => {
@@ -589,11 +723,44 @@ fn main() {
                 run_generic_module!(theme, ThemeInfo, get_theme, known_outputs.theme, config, log_errors, output, &mut gtk_settings_cache);
                 print_bench_time(args.benchmark, args.benchmark_warn, "Theme Module", bench);
             },
+            "network" => {
+                let bench: Option<Instant> = benchmark_point(args.benchmark); 
+                run_multiline_module!(network, NetworkInfo, get_network, known_outputs.network, config, log_errors, output, &config, &mut syscall_cache);
+                print_bench_time(args.benchmark, args.benchmark_warn, "Network Module", bench);
+            },
+            "netspeed" => {
+                let bench: Option<Instant> = benchmark_point(args.benchmark); 
+                run_multiline_module!(netspeed, NetSpeedInfo, get_netspeed, known_outputs.netspeed, config, log_errors, output, &config);
+                print_bench_time(args.benchmark, args.benchmark_warn, "NetSpeed Module", bench);
+            },
             "icontheme" => {
                 let bench: Option<Instant> = benchmark_point(args.benchmark); 
                 run_generic_module!(icon_theme, IconThemeInfo, get_icon_theme, known_outputs.icon_theme, config, log_errors, output, &mut gtk_settings_cache);
                 print_bench_time(args.benchmark, args.benchmark_warn, "Icon Theme Module", bench);
             },
+            "security_cpu" => {
+                let bench: Option<Instant> = benchmark_point(args.benchmark); 
+                if known_outputs.security_cpu.is_none() {
+                    known_outputs.security_cpu = Some(security_cpu::get_cpu_security(&config));
+                }
+                match known_outputs.security_cpu.as_ref().unwrap() {
+                    Ok(security) => {
+                        if config.security_cpu.list_entries {
+                            output.append(&mut security.style_entries(&config));
+                        } else {
+                            output.push(security.style(&config));
+                        }
+                    },
+                    Err(e) => {
+                        if log_errors {
+                            output.push((String::new(), e.to_string()));
+                        } else {
+                            output.push(SecurityCPUInfo::unknown_output(&config));
+                        }
+                    },
+                }
+                print_bench_time(args.benchmark, args.benchmark_warn, "CPU Security Module", bench);
+            },
 
             // i hate what's below as well, don't worry
             "colors" => {
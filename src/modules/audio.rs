@@ -0,0 +1,223 @@
+use std::{fs::{self, ReadDir}, path::Path};
+
+use serde::Deserialize;
+
+use crate::{common_sources::package_managers::ManagerInfo, config_manager::Configuration, formatter::CrabFetchColor, module::Module, proccess_info::ProcessInfo, util::{self, is_flag_set_u32}, versions, ModuleError};
+
+pub struct AudioInfo {
+    server: String,         // PipeWire / PulseAudio / JACK
+    server_version: String,
+    cards: Vec<SoundCard>
+}
+pub struct SoundCard {
+    index: u32,
+    id: String,     // PCH
+    driver: String, // HDA-Intel
+    name: String    // HDA Intel PCH
+}
+#[derive(Deserialize)]
+pub struct AudioConfiguration {
+    pub title: String,
+    pub title_color: Option<CrabFetchColor>,
+    pub title_bold: Option<bool>,
+    pub title_italic: Option<bool>,
+    pub separator: Option<String>,
+    pub format: String,
+    pub list_cards: bool,
+    pub card_title: String,
+    pub card_format: String
+}
+impl Module for AudioInfo {
+    fn new() -> AudioInfo {
+        AudioInfo {
+            server: "Unknown".to_string(),
+            server_version: "Unknown".to_string(),
+            cards: Vec::new()
+        }
+    }
+
+    fn style(&self, config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.audio.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.audio.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.audio.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.audio.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = self.replace_placeholders(&config.audio.title, config);
+        let value: String = self.replace_color_placeholders(&self.replace_placeholders(&config.audio.format, config), config);
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value)
+    }
+    fn unknown_output(config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.audio.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.audio.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.audio.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.audio.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = config.audio.title
+            .replace("{server}", "Unknown")
+            .replace("{server_version}", "Unknown")
+            .replace("{cards}", "Unknown")
+            .replace("{card_count}", "Unknown");
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
+    }
+
+    fn replace_placeholders(&self, text: &str, _: &Configuration) -> String {
+        text.replace("{server}", &self.server)
+            .replace("{server_version}", &self.server_version)
+            .replace("{cards}", &self.cards.iter().map(|x| x.name.as_str()).collect::<Vec<&str>>().join(", "))
+            .replace("{card_count}", &self.cards.len().to_string())
+    }
+
+    fn gen_info_flags(format: &str) -> u32 {
+        let mut info_flags: u32 = 0;
+
+        if format.contains("{server}") {
+            info_flags |= AUDIO_INFOFLAG_SERVER;
+        }
+        if format.contains("{server_version}") {
+            info_flags |= AUDIO_INFOFLAG_SERVER | AUDIO_INFOFLAG_SERVER_VERSION;
+        }
+        if format.contains("{cards}") || format.contains("{card_count}") {
+            info_flags |= AUDIO_INFOFLAG_CARDS;
+        }
+
+        info_flags
+    }
+}
+impl AudioInfo {
+    // Used when list_cards is on, styles each sound card onto it's own line
+    // Falls back to the regular output when there's no sound cards
+    pub fn style_cards(&self, config: &Configuration) -> Vec<(String, String)> {
+        if self.cards.is_empty() {
+            return vec![self.style(config)];
+        }
+
+        let title_color: &CrabFetchColor = config.audio.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.audio.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.audio.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.audio.separator.as_ref().unwrap_or(&config.separator);
+
+        let mut lines: Vec<(String, String)> = Vec::new();
+        for card in &self.cards {
+            let title: String = self.replace_card_placeholders(card, &config.audio.card_title, config);
+            let value: String = self.replace_color_placeholders(&self.replace_card_placeholders(card, &config.audio.card_format, config), config);
+            lines.push(Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value));
+        }
+
+        lines
+    }
+
+    fn replace_card_placeholders(&self, card: &SoundCard, text: &str, config: &Configuration) -> String {
+        self.replace_placeholders(text, config)
+            .replace("{index}", &card.index.to_string())
+            .replace("{id}", &card.id)
+            .replace("{driver}", &card.driver)
+            .replace("{name}", &card.name)
+    }
+}
+
+const AUDIO_INFOFLAG_SERVER: u32 = 1;
+const AUDIO_INFOFLAG_SERVER_VERSION: u32 = 2;
+const AUDIO_INFOFLAG_CARDS: u32 = 4;
+
+// Process names of each audio server, in order of priority, alongside the server's main binary
+// The pipewire daemon is also used for screen sharing/video alongside a real PulseAudio, so it only
+// counts as the audio server over PulseAudio when pipewire-pulse is there to replace it
+// The binary is what gets asked for the version, as pipewire-pulse and jackdbus don't give a
+// usable one themselves
+const AUDIO_SERVERS: &[(&str, &str, &str)] = &[
+    ("pipewire-pulse", "PipeWire", "pipewire"),
+    ("pulseaudio", "PulseAudio", "pulseaudio"),
+    ("pipewire", "PipeWire", "pipewire"),
+    ("jackd", "JACK", "jackd"),
+    ("jackdbus", "JACK", "jackd")
+];
+
+pub fn get_audio(config: &Configuration, package_managers: &ManagerInfo) -> Result<AudioInfo, ModuleError> {
+    let mut audio: AudioInfo = AudioInfo::new();
+    // title is tagged onto the end here to account for the title placeholders
+    let mut info_flags: u32 = AudioInfo::gen_info_flags(&format!("{}{}", config.audio.format, config.audio.title));
+    if config.audio.list_cards {
+        info_flags |= AudioInfo::gen_info_flags(&format!("{}{}", config.audio.card_format, config.audio.card_title)) | AUDIO_INFOFLAG_CARDS;
+    }
+
+    if is_flag_set_u32(info_flags, AUDIO_INFOFLAG_CARDS) {
+        audio.cards = read_sound_cards()?;
+    }
+
+    if is_flag_set_u32(info_flags, AUDIO_INFOFLAG_SERVER) {
+        if let Some((name, binary, mut process)) = find_audio_server() {
+            audio.server = name.to_string();
+            if is_flag_set_u32(info_flags, AUDIO_INFOFLAG_SERVER_VERSION) {
+                // The main binary is expected to sit next to whichever process we found
+                // If it doesn't, there's nothing safe to run for the version
+                if let Some(path) = process.get_exe(false).ok().map(|x| Path::new(&x).with_file_name(binary)).filter(|x| x.exists()) {
+                    audio.server_version = versions::find_version(&path.to_string_lossy(), Some(binary), package_managers).unwrap_or("Unknown".to_string());
+                }
+            }
+        }
+    }
+
+    Ok(audio)
+}
+
+fn read_sound_cards() -> Result<Vec<SoundCard>, ModuleError> {
+    // Each card takes two lines, the first of which looks like;
+    //  0 [PCH            ]: HDA-Intel - HDA Intel PCH
+    // https://www.kernel.org/doc/html/latest/sound/designs/procfile.html
+    let contents: String = match util::file_read(Path::new("/proc/asound/cards")) {
+        Ok(r) => r,
+        Err(e) => return Err(ModuleError::new("Audio", format!("Can't read from /proc/asound/cards - {e}"))),
+    };
+
+    let mut cards: Vec<SoundCard> = Vec::new();
+    for line in contents.lines() {
+        let Some((index, rest)) = line.trim_start().split_once(' ') else {
+            continue;
+        };
+        let Ok(index) = index.parse::<u32>() else {
+            continue; // The second line of a card, or "--- no soundcards ---"
+        };
+        let Some((id, description)) = rest.split_once("]:") else {
+            continue;
+        };
+        let (driver, name) = description.split_once(" - ").unwrap_or((description, description));
+
+        cards.push(SoundCard {
+            index,
+            id: id.trim_start_matches([' ', '[']).trim().to_string(),
+            driver: driver.trim().to_string(),
+            name: name.trim().to_string()
+        });
+    }
+
+    Ok(cards)
+}
+
+fn find_audio_server() -> Option<(&'static str, &'static str, ProcessInfo)> {
+    let dir: ReadDir = fs::read_dir("/proc").ok()?;
+
+    let mut found: Option<(usize, ProcessInfo)> = None;
+    for entry in dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Some(pid) = entry.file_name().to_str().and_then(|x| x.parse::<u32>().ok()) else {
+            continue;
+        };
+
+        let mut process: ProcessInfo = ProcessInfo::new(pid);
+        let Ok(name) = process.get_process_name() else {
+            continue; // Not ours, or a kernel thread
+        };
+        let Some(priority) = AUDIO_SERVERS.iter().position(|x| x.0 == name) else {
+            continue;
+        };
+        if found.as_ref().is_none_or(|x| priority < x.0) {
+            found = Some((priority, process));
+        }
+    }
+
+    found.map(|(priority, process)| (AUDIO_SERVERS[priority].1, AUDIO_SERVERS[priority].2, process))
+}
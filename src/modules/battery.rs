@@ -1,5 +1,6 @@
-use std::{fs::{self, DirEntry, ReadDir}, path::PathBuf};
+use std::{fs::{self, DirEntry, ReadDir}, path::{Path, PathBuf}, time::Duration};
 
+use humantime::format_duration;
 use serde::Deserialize;
 
 use crate::{config_manager::Configuration, formatter::{self, CrabFetchColor}, module::Module, util, ModuleError};
@@ -7,6 +8,22 @@ use crate::{config_manager::Configuration, formatter::{self, CrabFetchColor}, mo
 pub struct BatteryInfo {
     index: String,
     percentage: f32,
+    battery_type: String,           // Battery / UPS
+    status: String,                 // Charging / Discharging / Full / Not charging
+    health: Option<f32>,            // Current full capacity vs it's design capacity
+    time_remaining: Option<Duration>,
+    power_draw: Option<f64>,        // Watts
+    manufacturer: String,
+    model: String,
+    cycle_count: Option<u32>,
+    ac_online: Option<bool>
+}
+#[derive(Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatteryDevices {
+    System,
+    Peripheral,
+    All
 }
 #[derive(Deserialize)]
 pub struct BatteryConfiguration {
@@ -22,12 +39,22 @@ pub struct BatteryConfiguration {
     pub progress_empty: Option<String>,
     pub progress_target_length: Option<u8>,
     pub decimal_places: Option<u32>,
+    pub devices: BatteryDevices
 }
 impl Module for BatteryInfo {
     fn new() -> BatteryInfo {
         BatteryInfo {
             index: "Unknown".to_string(),
-            percentage: 0.0
+            percentage: 0.0,
+            battery_type: "Unknown".to_string(),
+            status: "Unknown".to_string(),
+            health: None,
+            time_remaining: None,
+            power_draw: None,
+            manufacturer: "Unknown".to_string(),
+            model: "Unknown".to_string(),
+            cycle_count: None,
+            ac_online: None
         }
     }
 
@@ -51,7 +78,16 @@ impl Module for BatteryInfo {
         let title: String = config.battery.title
             .replace("{index}", "0").to_string()
             .replace("{percentage}", "Unknown").to_string()
-            .replace("{bar}", "").to_string();
+            .replace("{bar}", "").to_string()
+            .replace("{type}", "Unknown")
+            .replace("{status}", "Unknown")
+            .replace("{health}", "Unknown")
+            .replace("{time_remaining}", "Unknown")
+            .replace("{power}", "Unknown")
+            .replace("{manufacturer}", "Unknown")
+            .replace("{model}", "Unknown")
+            .replace("{cycle_count}", "Unknown")
+            .replace("{ac}", "Unknown");
 
         Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
     }
@@ -74,6 +110,19 @@ impl Module for BatteryInfo {
             .replace("{index}", &self.index)
             .replace("{percentage}", &self.percentage.to_string())
             .replace("{bar}", &bar)
+            .replace("{type}", &self.battery_type)
+            .replace("{status}", &self.status)
+            .replace("{health}", &self.health.map_or("Unknown".to_string(), |x| format!("{}%", formatter::round(f64::from(x), dec_places))))
+            .replace("{time_remaining}", &self.time_remaining.map_or("Unknown".to_string(), |x| format_duration(x).to_string()))
+            .replace("{power}", &self.power_draw.map_or("Unknown".to_string(), |x| format!("{} W", formatter::round(x, dec_places))))
+            .replace("{manufacturer}", &self.manufacturer)
+            .replace("{model}", &self.model)
+            .replace("{cycle_count}", &self.cycle_count.map_or("Unknown".to_string(), |x| x.to_string()))
+            .replace("{ac}", match self.ac_online {
+                Some(true) => "Connected",
+                Some(false) => "Disconnected",
+                None => "Unknown"
+            })
     }
 
     fn gen_info_flags(_: &str) -> u32 {
@@ -81,20 +130,30 @@ impl Module for BatteryInfo {
     }
 }
 
-pub fn get_batteries() -> Result<Vec<BatteryInfo>, ModuleError> {
+pub fn get_batteries(config: &Configuration) -> Result<Vec<BatteryInfo>, ModuleError> {
     let mut batteries: Vec<BatteryInfo> = Vec::new();
 
+    // https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-power
     let dir: ReadDir = match fs::read_dir("/sys/class/power_supply/") {
         Ok(r) => r,
         Err(e) => return Err(ModuleError::new("Battery", format!("Can't read from /sys/class/power_supply: {e}"))),
     };
+    let mut ac_online: Option<bool> = None;
     for d in dir {
         if d.is_err() {
             continue;
         }
         let d: DirEntry = d.unwrap();
         let path: PathBuf = d.path();
-        // From what I can tell, this dir is only batteries so parsing is easy
+
+        // AC adapters live in here too, and we use them for whether the system is plugged in
+        let supply_type: String = read_value(&path, "type").unwrap_or("Unknown".to_string());
+        if supply_type == "Mains" {
+            let online: bool = read_value(&path, "online").is_some_and(|x| x == "1");
+            ac_online = Some(ac_online.unwrap_or(false) || online);
+            continue;
+        }
+
         let percentage: f32 = match util::file_read(&path.join("capacity")) {
             Ok(r) => {
                 match r.trim().parse() {
@@ -106,6 +165,15 @@ pub fn get_batteries() -> Result<Vec<BatteryInfo>, ModuleError> {
             // Err(e) => return Err(ModuleError::new("Battery", format!("Can't read from {} - {}", path, e))),
             Err(_) => continue,
         };
+
+        // Batteries in mice, headsets, controllers etc are marked with a "Device" scope
+        let is_peripheral: bool = read_value(&path, "scope").is_some_and(|x| x == "Device");
+        match config.battery.devices {
+            BatteryDevices::System if is_peripheral => continue,
+            BatteryDevices::Peripheral if !is_peripheral => continue,
+            _ => {}
+        }
+
         let id: String = match path.file_name() {
             Some(r) => match r.to_str() {
                 Some(r) => r.strip_prefix("BAT").unwrap_or(r).to_string(),
@@ -114,12 +182,85 @@ pub fn get_batteries() -> Result<Vec<BatteryInfo>, ModuleError> {
             None => continue,
         };
 
-        batteries.push(BatteryInfo {
-            index: id,
-            percentage
-        });
+        let mut battery: BatteryInfo = BatteryInfo::new();
+        battery.index = id;
+        battery.percentage = percentage;
+        battery.battery_type = supply_type;
+        if let Some(status) = read_value(&path, "status") {
+            battery.status = status;
+        }
+        if let Some(manufacturer) = read_value(&path, "manufacturer") {
+            battery.manufacturer = manufacturer;
+        }
+        if let Some(model) = read_value(&path, "model_name") {
+            battery.model = model;
+        }
+        // Some drivers report 0 when they don't track it
+        battery.cycle_count = read_number(&path, "cycle_count").and_then(|x| u32::try_from(x).ok()).filter(|x| *x != 0);
+        fill_energy(&path, &mut battery);
+
+        batteries.push(battery);
     }
 
+    for battery in &mut batteries {
+        battery.ac_online = ac_online;
+    }
 
     Ok(batteries)
 }
+
+// Works out the health, power draw and time remaining
+// Batteries report either energy in µWh and power in µW, or charge in µAh and current in µA
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn fill_energy(path: &Path, battery: &mut BatteryInfo) {
+    // Energy is in µWh with power in µW, where as charge is in µAh with current in µA
+    let uses_energy: bool = path.join("energy_now").exists();
+    let (now, full, full_design, rate) = if uses_energy {
+        (read_number(path, "energy_now"), read_number(path, "energy_full"), read_number(path, "energy_full_design"), read_number(path, "power_now"))
+    } else {
+        (read_number(path, "charge_now"), read_number(path, "charge_full"), read_number(path, "charge_full_design"), read_number(path, "current_now"))
+    };
+
+    if let (Some(full), Some(full_design)) = (full, full_design) {
+        if full_design != 0 {
+            battery.health = Some((full as f64 / full_design as f64 * 100.0) as f32);
+        }
+    }
+
+    // Current is signed on some drivers, and negative while discharging
+    let Some(rate) = rate.map(i64::unsigned_abs).filter(|x| *x != 0) else {
+        return;
+    };
+    battery.power_draw = if uses_energy {
+        Some(rate as f64 / 1_000_000.0)
+    } else {
+        // µA * µV = pW
+        read_number(path, "voltage_now").map(|x| rate as f64 * x.unsigned_abs() as f64 / 1_000_000_000_000.0)
+    };
+
+    let Some(now) = now.map(i64::unsigned_abs) else {
+        return;
+    };
+    let remaining: Option<u64> = match battery.status.as_str() {
+        "Discharging" => Some(now),
+        "Charging" => full.map(|x| x.unsigned_abs().saturating_sub(now)),
+        _ => None
+    };
+    if let Some(remaining) = remaining {
+        // Both are in the same unit, so this gives hours. Rounded to the minute to stay readable.
+        let minutes: u64 = (remaining as f64 / rate as f64 * 60.0) as u64;
+        battery.time_remaining = Some(Duration::from_secs(minutes * 60));
+    }
+}
+
+fn read_value(path: &Path, file: &str) -> Option<String> {
+    let value: String = util::file_read(&path.join(file)).ok()?;
+    let value: &str = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    Some(value.to_string())
+}
+fn read_number(path: &Path, file: &str) -> Option<i64> {
+    read_value(path, file)?.parse::<i64>().ok()
+}
@@ -0,0 +1,154 @@
+use std::{fs::{self, ReadDir}, path::Path};
+
+use serde::Deserialize;
+
+use crate::{config_manager::Configuration, formatter::{self, CrabFetchColor}, module::Module, util, ModuleError};
+
+pub struct BrightnessInfo {
+    name: String,           // intel_backlight / tpacpi::kbd_backlight
+    device_type: String,    // Display / Keyboard
+    brightness: u64,
+    max_brightness: u64,
+    percent: f32
+}
+#[derive(Deserialize)]
+pub struct BrightnessConfiguration {
+    pub title: String,
+    pub title_color: Option<CrabFetchColor>,
+    pub title_bold: Option<bool>,
+    pub title_italic: Option<bool>,
+    pub separator: Option<String>,
+    pub format: String,
+    pub progress_left_border: Option<String>,
+    pub progress_right_border: Option<String>,
+    pub progress_progress: Option<String>,
+    pub progress_empty: Option<String>,
+    pub progress_target_length: Option<u8>,
+    pub decimal_places: Option<u32>,
+    pub show_keyboard: bool
+}
+impl Module for BrightnessInfo {
+    fn new() -> BrightnessInfo {
+        BrightnessInfo {
+            name: "Unknown".to_string(),
+            device_type: "Unknown".to_string(),
+            brightness: 0,
+            max_brightness: 0,
+            percent: 0.0
+        }
+    }
+
+    fn style(&self, config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.brightness.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.brightness.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.brightness.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.brightness.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = self.replace_placeholders(&config.brightness.title, config);
+        let value: String = self.replace_color_placeholders(&self.replace_placeholders(&config.brightness.format, config), config);
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value)
+    }
+    fn unknown_output(config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.brightness.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.brightness.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.brightness.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.brightness.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = config.brightness.title
+            .replace("{name}", "Unknown")
+            .replace("{type}", "Unknown")
+            .replace("{brightness}", "Unknown")
+            .replace("{max_brightness}", "Unknown")
+            .replace("{percent}", "Unknown")
+            .replace("{bar}", "");
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
+    }
+
+    fn replace_placeholders(&self, text: &str, config: &Configuration) -> String {
+        let dec_places: u32 = config.brightness.decimal_places.unwrap_or(config.decimal_places);
+
+        let mut bar: String = String::new();
+        if text.contains("{bar}") {
+            let left_border: &str = config.brightness.progress_left_border.as_ref().unwrap_or(&config.progress_left_border);
+            let right_border: &str = config.brightness.progress_right_border.as_ref().unwrap_or(&config.progress_right_border);
+            let progress: &str = config.brightness.progress_progress.as_ref().unwrap_or(&config.progress_progress);
+            let empty: &str = config.brightness.progress_empty.as_ref().unwrap_or(&config.progress_empty);
+            let length: u8 = config.brightness.progress_target_length.unwrap_or(config.progress_target_length);
+            formatter::make_bar(&mut bar, left_border, right_border, progress, empty, self.percent, length);
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        formatter::process_percentage_placeholder(text, formatter::round(f64::from(self.percent), dec_places) as f32, config)
+            .replace("{name}", &self.name)
+            .replace("{type}", &self.device_type)
+            .replace("{brightness}", &self.brightness.to_string())
+            .replace("{max_brightness}", &self.max_brightness.to_string())
+            .replace("{bar}", &bar)
+    }
+
+    fn gen_info_flags(_: &str) -> u32 {
+        panic!("gen_info_flags called on brightness module. This should never happen, please make a bug report!")
+    }
+}
+
+pub fn get_brightness(config: &Configuration) -> Result<Vec<BrightnessInfo>, ModuleError> {
+    let mut devices: Vec<BrightnessInfo> = Vec::new();
+
+    // https://www.kernel.org/doc/Documentation/ABI/stable/sysfs-class-backlight
+    // Not having any backlights is normal on desktops, so this isn't an error
+    if let Ok(dir) = fs::read_dir("/sys/class/backlight") {
+        read_devices(dir, "Display", &mut devices)?;
+    }
+
+    // Keyboard backlights are LEDs instead, with a name ending in kbd_backlight
+    // https://www.kernel.org/doc/Documentation/leds/leds-class.rst
+    if config.brightness.show_keyboard {
+        if let Ok(dir) = fs::read_dir("/sys/class/leds") {
+            read_devices(dir, "Keyboard", &mut devices)?;
+        }
+    }
+
+    Ok(devices)
+}
+
+fn read_devices(dir: ReadDir, device_type: &str, devices: &mut Vec<BrightnessInfo>) -> Result<(), ModuleError> {
+    let mut found: Vec<BrightnessInfo> = Vec::new();
+    for entry in dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let name: String = entry.file_name().to_string_lossy().to_string();
+        if device_type == "Keyboard" && !name.ends_with("::kbd_backlight") {
+            continue;
+        }
+
+        let brightness: u64 = read_value(&entry.path(), "brightness")?;
+        let max_brightness: u64 = read_value(&entry.path(), "max_brightness")?;
+        if max_brightness == 0 {
+            continue;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let percent: f32 = (brightness as f32 / max_brightness as f32) * 100.0;
+        found.push(BrightnessInfo {
+            name,
+            device_type: device_type.to_string(),
+            brightness,
+            max_brightness,
+            percent
+        });
+    }
+
+    found.sort_by(|a, b| a.name.cmp(&b.name));
+    devices.append(&mut found);
+    Ok(())
+}
+
+fn read_value(path: &Path, file: &str) -> Result<u64, ModuleError> {
+    match util::file_read(&path.join(file)) {
+        Ok(r) => Ok(r.trim().parse::<u64>().unwrap_or(0)),
+        Err(e) => Err(ModuleError::new("Brightness", format!("Can't read from {}/{file} - {e}", path.display()))),
+    }
+}
@@ -7,7 +7,7 @@ use {android_system_properties::AndroidSystemProperties, std::env};
 use raw_cpuid::CpuId;
 use serde::Deserialize;
 
-use crate::{config_manager::Configuration, formatter::{self, CrabFetchColor}, module::Module, util::{self, is_flag_set_u32}, ModuleError};
+use crate::{common_sources::cgroups, config_manager::Configuration, formatter::{self, CrabFetchColor}, module::Module, util::{self, is_flag_set_u32}, ModuleError};
 
 pub struct CPUInfo {
     name: String,
@@ -15,7 +15,8 @@ pub struct CPUInfo {
     threads: u16,
     current_clock_mhz: f32,
     max_clock_mhz: f32,
-    arch: String
+    arch: String,
+    cgroup_cpus: Option<f64>
 }
 #[derive(Deserialize)]
 pub struct CPUConfiguration {
@@ -26,7 +27,8 @@ pub struct CPUConfiguration {
     pub separator: Option<String>,
     pub format: String,
     pub decimal_places: Option<u32>,
-    pub remove_trailing_processor: bool
+    pub remove_trailing_processor: bool,
+    pub use_cgroup_limits: bool
 }
 
 impl Module for CPUInfo {
@@ -37,7 +39,8 @@ impl Module for CPUInfo {
             threads: 0,
             current_clock_mhz: 0.0,
             max_clock_mhz: 0.0,
-            arch: "Unknown".to_string()
+            arch: "Unknown".to_string(),
+            cgroup_cpus: None
         }
     }
 
@@ -66,13 +69,18 @@ impl Module for CPUInfo {
             .replace("{current_clock_ghz}", "Unknown")
             .replace("{max_clock_mhz}", "Unknown")
             .replace("{max_clock_ghz}", "Unknown")
-            .replace("{arch}", "Unknown");
+            .replace("{arch}", "Unknown")
+            .replace("{cgroup_cpus}", "Unknown");
         
         Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
     }
 
     fn replace_placeholders(&self, text: &str, config: &Configuration) -> String {
         let dec_places: u32 = config.cpu.decimal_places.unwrap_or(config.decimal_places);
+        let cgroup_cpus: String = match self.cgroup_cpus {
+            Some(r) => formatter::round(r, dec_places).to_string(),
+            None => "Unlimited".to_string(),
+        };
 
         text.replace("{name}", &self.name)
             .replace("{core_count}", &self.cores.to_string())
@@ -82,6 +90,7 @@ impl Module for CPUInfo {
             .replace("{max_clock_mhz}", &formatter::round(f64::from(self.max_clock_mhz), dec_places).to_string())
             .replace("{max_clock_ghz}", &formatter::round(f64::from(self.max_clock_mhz / 1000.0), dec_places).to_string())
             .replace("{arch}", &self.arch.to_string())
+            .replace("{cgroup_cpus}", &cgroup_cpus)
     }
 
     fn gen_info_flags(format: &str) -> u32 {
@@ -106,6 +115,9 @@ impl Module for CPUInfo {
         if format.contains("{arch}") || format.contains("{arch}") {
             info_flags |= CPU_INFOFLAG_ARCH;
         }
+        if format.contains("{cgroup_cpus}") {
+            info_flags |= CPU_INFOFLAG_CGROUP;
+        }
 
         info_flags
     }
@@ -117,6 +129,7 @@ const CPU_INFOFLAG_THREADS: u32 = 4;
 const CPU_INFOFLAG_CURRENT_CLOCK: u32 = 8;
 const CPU_INFOFLAG_MAX_CLOCK: u32 = 16;
 const CPU_INFOFLAG_ARCH: u32 = 32;
+const CPU_INFOFLAG_CGROUP: u32 = 64;
 
 pub fn get_cpu(config: &Configuration) -> Result<CPUInfo, ModuleError> {
     let mut cpu: CPUInfo = CPUInfo::new();
@@ -132,6 +145,19 @@ pub fn get_cpu(config: &Configuration) -> Result<CPUInfo, ModuleError> {
         Err(e) => return Err(e)
     };
 
+    // Container limits
+    if is_flag_set_u32(info_flags, CPU_INFOFLAG_CGROUP) || config.cpu.use_cgroup_limits {
+        cpu.cgroup_cpus = cgroups::get_cpu_limit();
+    }
+    if config.cpu.use_cgroup_limits && is_flag_set_u32(info_flags, CPU_INFOFLAG_THREADS) {
+        if let Some(cgroup_cpus) = cpu.cgroup_cpus {
+            // Partial CPU's still need a thread to run on
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let cgroup_threads: u16 = cgroup_cpus.ceil().max(1.0) as u16;
+            cpu.threads = cpu.threads.min(cgroup_threads);
+        }
+    }
+
     if config.cpu.remove_trailing_processor {
         // Tried doing this with Regex but it added 400 micro secs so fuck that shit
         let loc: usize = match cpu.name.find("-Core Processor") {
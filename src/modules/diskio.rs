@@ -0,0 +1,185 @@
+use std::{path::Path, thread, time::{Duration, Instant}};
+
+use serde::Deserialize;
+
+use crate::{config_manager::Configuration, disks, formatter::{self, CrabFetchColor}, module::Module, util, ModuleError};
+
+pub struct DiskIOInfo {
+    name: String,
+    read_kb_per_sec: f64,
+    write_kb_per_sec: f64,
+    read_total_kb: u64,
+    write_total_kb: u64,
+    busy_percent: f32
+}
+#[derive(Deserialize)]
+pub struct DiskIOConfiguration {
+    pub title: String,
+    pub title_color: Option<CrabFetchColor>,
+    pub title_bold: Option<bool>,
+    pub title_italic: Option<bool>,
+    pub separator: Option<String>,
+    pub progress_left_border: Option<String>,
+    pub progress_right_border: Option<String>,
+    pub progress_progress: Option<String>,
+    pub progress_empty: Option<String>,
+    pub progress_target_length: Option<u8>,
+    pub decimal_places: Option<u32>,
+    pub use_ibis: Option<bool>,
+    pub format: String,
+    pub interval_ms: u64,
+    pub ignore: Vec<String>
+}
+impl Module for DiskIOInfo {
+    fn new() -> DiskIOInfo {
+        DiskIOInfo {
+            name: "Unknown".to_string(),
+            read_kb_per_sec: 0.0,
+            write_kb_per_sec: 0.0,
+            read_total_kb: 0,
+            write_total_kb: 0,
+            busy_percent: 0.0
+        }
+    }
+
+    fn style(&self, config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.diskio.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.diskio.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.diskio.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.diskio.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = self.replace_placeholders(&config.diskio.title, config);
+        let value: String = self.replace_color_placeholders(&self.replace_placeholders(&config.diskio.format, config), config);
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value)
+    }
+    fn unknown_output(config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.diskio.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.diskio.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.diskio.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.diskio.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = config.diskio.title
+            .replace("{name}", "Unknown")
+            .replace("{read}", "Unknown")
+            .replace("{write}", "Unknown")
+            .replace("{read_total}", "Unknown")
+            .replace("{write_total}", "Unknown")
+            .replace("{bar}", "Unknown")
+            .replace("{percent}", "Unknown");
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
+    }
+
+    fn replace_placeholders(&self, text: &str, config: &Configuration) -> String {
+        let dec_places: u32 = config.diskio.decimal_places.unwrap_or(config.decimal_places);
+        let use_ibis: bool = config.diskio.use_ibis.unwrap_or(config.use_ibis);
+
+        let mut bar: String = String::new();
+        if text.contains("{bar}") {
+            let left_border: &str = config.diskio.progress_left_border.as_ref().unwrap_or(&config.progress_left_border);
+            let right_border: &str = config.diskio.progress_right_border.as_ref().unwrap_or(&config.progress_right_border);
+            let progress: &str = config.diskio.progress_progress.as_ref().unwrap_or(&config.progress_progress);
+            let empty: &str = config.diskio.progress_empty.as_ref().unwrap_or(&config.progress_empty);
+            let length: u8 = config.diskio.progress_target_length.unwrap_or(config.progress_target_length);
+            formatter::make_bar(&mut bar, left_border, right_border, progress, empty, self.busy_percent, length);
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        formatter::process_percentage_placeholder(text, formatter::round(f64::from(self.busy_percent), dec_places) as f32, config)
+            .replace("{name}", &self.name)
+            .replace("{read}", &formatter::auto_format_bytes_f64(self.read_kb_per_sec, use_ibis, dec_places))
+            .replace("{write}", &formatter::auto_format_bytes_f64(self.write_kb_per_sec, use_ibis, dec_places))
+            .replace("{read_total}", &formatter::auto_format_bytes(self.read_total_kb, use_ibis, dec_places))
+            .replace("{write_total}", &formatter::auto_format_bytes(self.write_total_kb, use_ibis, dec_places))
+            .replace("{bar}", &bar)
+    }
+
+    fn gen_info_flags(_: &str) -> u32 {
+        panic!("gen_info_flags called on disk IO module. This should never happen, please make a bug report!")
+    }
+}
+
+// The counters we care about from a line of /proc/diskstats
+// https://www.kernel.org/doc/Documentation/ABI/testing/procfs-diskstats
+struct DiskStats {
+    name: String,
+    sectors_read: u64,
+    sectors_written: u64,
+    io_ms: u64          // Time spent with at least one request in flight
+}
+
+pub fn get_disk_io(config: &Configuration) -> Result<Vec<DiskIOInfo>, ModuleError> {
+    let mut disks: Vec<DiskIOInfo> = Vec::new();
+
+    let first: Vec<DiskStats> = read_diskstats(config)?;
+    let start: Instant = Instant::now();
+    thread::sleep(Duration::from_millis(config.diskio.interval_ms));
+    // Use the real time that passed in case we got held up
+    let elapsed_ms: f64 = (start.elapsed().as_secs_f64() * 1000.0).max(1.0);
+    let second: Vec<DiskStats> = read_diskstats(config)?;
+
+    for stats in second {
+        let Some(previous) = first.iter().find(|x| x.name == stats.name) else {
+            continue; // Appeared between samples
+        };
+
+        // Sectors are always 512 bytes here, regardless of the disk's actual sector size
+        // Bytes per ms is the same as kilobytes per second
+        #[allow(clippy::cast_precision_loss)]
+        let (read_kb_per_sec, write_kb_per_sec): (f64, f64) = (
+            (stats.sectors_read.saturating_sub(previous.sectors_read) * 512) as f64 / elapsed_ms,
+            (stats.sectors_written.saturating_sub(previous.sectors_written) * 512) as f64 / elapsed_ms
+        );
+        #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+        let busy_percent: f32 = (stats.io_ms.saturating_sub(previous.io_ms) as f64 / elapsed_ms * 100.0).min(100.0) as f32;
+
+        disks.push(DiskIOInfo {
+            name: stats.name,
+            read_kb_per_sec,
+            write_kb_per_sec,
+            read_total_kb: stats.sectors_read * 512 / 1000,
+            write_total_kb: stats.sectors_written * 512 / 1000,
+            busy_percent
+        });
+    }
+
+    disks.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(disks)
+}
+
+fn read_diskstats(config: &Configuration) -> Result<Vec<DiskStats>, ModuleError> {
+    let contents: String = match util::file_read(Path::new("/proc/diskstats")) {
+        Ok(r) => r,
+        Err(e) => return Err(ModuleError::new("DiskIO", format!("Can't read from /proc/diskstats - {e}"))),
+    };
+
+    let mut stats: Vec<DiskStats> = Vec::new();
+    for line in contents.lines() {
+        let values: Vec<&str> = line.split_whitespace().collect();
+        if values.len() < 13 {
+            continue;
+        }
+
+        // Only whole, physical disks; partitions don't have an entry directly in /sys/block
+        let name: &str = values[2];
+        if !Path::new("/sys/block").join(name).exists() {
+            continue;
+        }
+        if disks::VIRTUAL_DEVICE_PREFIXES.iter().any(|x| name.starts_with(x)) {
+            continue;
+        }
+        if config.diskio.ignore.iter().any(|x| !x.is_empty() && name.starts_with(x)) {
+            continue;
+        }
+
+        stats.push(DiskStats {
+            name: name.to_string(),
+            sectors_read: values[5].parse::<u64>().unwrap_or(0),
+            sectors_written: values[9].parse::<u64>().unwrap_or(0),
+            io_ms: values[12].parse::<u64>().unwrap_or(0)
+        });
+    }
+
+    Ok(stats)
+}
@@ -0,0 +1,245 @@
+use std::{fs::{self, ReadDir}, path::{Path, PathBuf}};
+
+use serde::Deserialize;
+
+use crate::{config_manager::Configuration, formatter::{self, CrabFetchColor}, module::Module, util::{self, is_flag_set_u32}, ModuleError};
+
+pub struct DiskInfo {
+    name: String,       // sda / nvme0n1
+    model: String,
+    vendor: String,
+    serial: String,
+    size_kb: u64,
+    disk_type: String,  // HDD / SSD / NVMe
+    removable: bool,
+    transport: String   // sata / usb / nvme / ...
+}
+#[derive(Deserialize)]
+pub struct DiskConfiguration {
+    pub title: String,
+    pub title_color: Option<CrabFetchColor>,
+    pub title_bold: Option<bool>,
+    pub title_italic: Option<bool>,
+    pub separator: Option<String>,
+    pub format: String,
+    pub decimal_places: Option<u32>,
+    pub use_ibis: Option<bool>,
+    pub show_serial: bool,
+    pub ignore: Vec<String>
+}
+impl Module for DiskInfo {
+    fn new() -> DiskInfo {
+        DiskInfo {
+            name: "Unknown".to_string(),
+            model: "Unknown".to_string(),
+            vendor: "Unknown".to_string(),
+            serial: "Unknown".to_string(),
+            size_kb: 0,
+            disk_type: "Unknown".to_string(),
+            removable: false,
+            transport: "Unknown".to_string()
+        }
+    }
+
+    fn style(&self, config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.disks.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.disks.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.disks.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.disks.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = self.replace_placeholders(&config.disks.title, config);
+        let value: String = self.replace_color_placeholders(&self.replace_placeholders(&config.disks.format, config), config);
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value)
+    }
+    fn unknown_output(config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.disks.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.disks.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.disks.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.disks.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = config.disks.title
+            .replace("{name}", "Unknown")
+            .replace("{model}", "Unknown")
+            .replace("{vendor}", "Unknown")
+            .replace("{serial}", "Unknown")
+            .replace("{size}", "Unknown")
+            .replace("{type}", "Unknown")
+            .replace("{removable}", "Unknown")
+            .replace("{transport}", "Unknown");
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
+    }
+
+    fn replace_placeholders(&self, text: &str, config: &Configuration) -> String {
+        let dec_places: u32 = config.disks.decimal_places.unwrap_or(config.decimal_places);
+        let use_ibis: bool = config.disks.use_ibis.unwrap_or(config.use_ibis);
+
+        text.replace("{name}", &self.name)
+            .replace("{model}", &self.model)
+            .replace("{vendor}", &self.vendor)
+            .replace("{serial}", &self.serial)
+            .replace("{size}", &formatter::auto_format_bytes(self.size_kb, use_ibis, dec_places))
+            .replace("{type}", &self.disk_type)
+            .replace("{removable}", if self.removable {"Removable"} else {"Fixed"})
+            .replace("{transport}", &self.transport)
+    }
+
+    fn gen_info_flags(format: &str) -> u32 {
+        let mut info_flags: u32 = 0;
+
+        if format.contains("{model}") {
+            info_flags |= DISKS_INFOFLAG_MODEL;
+        }
+        if format.contains("{vendor}") {
+            info_flags |= DISKS_INFOFLAG_VENDOR;
+        }
+        if format.contains("{serial}") {
+            info_flags |= DISKS_INFOFLAG_SERIAL;
+        }
+        if format.contains("{transport}") {
+            info_flags |= DISKS_INFOFLAG_TRANSPORT;
+        }
+
+        info_flags
+    }
+}
+
+const DISKS_INFOFLAG_MODEL: u32 = 1;
+const DISKS_INFOFLAG_VENDOR: u32 = 2;
+const DISKS_INFOFLAG_SERIAL: u32 = 4;
+const DISKS_INFOFLAG_TRANSPORT: u32 = 8;
+
+// Block devices that aren't backed by a physical drive
+pub const VIRTUAL_DEVICE_PREFIXES: &[&str] = &[
+    "loop",
+    "ram",
+    "zram",
+    "dm-"
+];
+
+pub fn get_disks(config: &Configuration) -> Result<Vec<DiskInfo>, ModuleError> {
+    let mut disks: Vec<DiskInfo> = Vec::new();
+    // title is tagged onto the end here to account for the title placeholders
+    let info_flags: u32 = DiskInfo::gen_info_flags(&format!("{}{}", config.disks.format, config.disks.title));
+
+    // Each entry in /sys/block is a whole disk, partitions live inside of them
+    let dir: ReadDir = match fs::read_dir("/sys/block") {
+        Ok(r) => r,
+        Err(e) => return Err(ModuleError::new("Disks", format!("Can't read from /sys/block: {e}"))),
+    };
+    for entry in dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let name: String = entry.file_name().to_string_lossy().to_string();
+        if VIRTUAL_DEVICE_PREFIXES.iter().any(|x| name.starts_with(x)) {
+            continue;
+        }
+        if config.disks.ignore.iter().any(|x| !x.is_empty() && name.starts_with(x)) {
+            continue;
+        }
+        let path: PathBuf = entry.path();
+
+        let mut disk: DiskInfo = DiskInfo::new();
+        // Always reported in 512 byte sectors, regardless of the actual sector size
+        // https://github.com/torvalds/linux/blob/master/include/linux/blk_types.h#L30
+        disk.size_kb = match util::file_read(&path.join("size")) {
+            Ok(r) => r.trim().parse::<u64>().unwrap_or(0) * 512 / 1000,
+            Err(e) => return Err(ModuleError::new("Disks", format!("Can't read from {}/size - {e}", path.display()))),
+        };
+        if disk.size_kb == 0 {
+            continue; // Empty card readers, disc drives with no disc, etc
+        }
+
+        disk.removable = util::file_read(&path.join("removable")).is_ok_and(|x| x.trim() == "1");
+        disk.disk_type = if name.starts_with("nvme") {
+            "NVMe".to_string()
+        } else if util::file_read(&path.join("queue/rotational")).is_ok_and(|x| x.trim() == "1") {
+            "HDD".to_string()
+        } else {
+            "SSD".to_string()
+        };
+
+        if is_flag_set_u32(info_flags, DISKS_INFOFLAG_MODEL) {
+            if let Some(model) = read_disk_model(&name) {
+                disk.model = model;
+            }
+        }
+        if is_flag_set_u32(info_flags, DISKS_INFOFLAG_VENDOR) {
+            if let Ok(vendor) = util::file_read(&path.join("device/vendor")) {
+                disk.vendor = vendor.trim().to_string();
+            }
+        }
+        if is_flag_set_u32(info_flags, DISKS_INFOFLAG_SERIAL) {
+            if config.disks.show_serial {
+                if let Ok(serial) = util::file_read(&path.join("device/serial")) {
+                    disk.serial = serial.trim().to_string();
+                }
+            } else {
+                disk.serial = "Hidden".to_string();
+            }
+        }
+        if is_flag_set_u32(info_flags, DISKS_INFOFLAG_TRANSPORT) {
+            disk.transport = find_transport(&path);
+        }
+
+        disk.name = name;
+        disks.push(disk);
+    }
+
+    disks.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(disks)
+}
+
+// Reads the model of a disk in /sys/block, e.g "sda"
+pub fn read_disk_model(disk: &str) -> Option<String> {
+    let model: String = util::file_read(&Path::new("/sys/block").join(disk).join("device/model")).ok()?;
+    let model: &str = model.trim();
+    if model.is_empty() {
+        return None;
+    }
+    Some(model.to_string())
+}
+
+// Finds the disk that a partition belongs to, e.g /dev/sda1 -> sda
+// Whole disks are given back as they are
+pub fn find_parent_disk(device: &str) -> Option<String> {
+    let name: &str = device.split('/').next_back()?;
+    let path: PathBuf = Path::new("/sys/class/block").join(name);
+    if !path.join("partition").exists() {
+        return Some(name.to_string());
+    }
+
+    // Partitions sit inside of their parent disk's directory
+    let real_path: PathBuf = fs::canonicalize(path).ok()?;
+    let parent: &Path = real_path.parent()?;
+    Some(parent.file_name()?.to_str()?.to_string())
+}
+
+fn find_transport(path: &Path) -> String {
+    // The kernel doesn't expose this directly (lsblk gets it from udev), so instead we look at
+    // what bus the device is hanging off of
+    let Ok(real_path) = fs::canonicalize(path) else {
+        return "Unknown".to_string();
+    };
+    let real_path: String = real_path.display().to_string();
+
+    let transport: &str = if real_path.contains("/usb") {
+        "usb"
+    } else if real_path.contains("/nvme/") {
+        "nvme"
+    } else if real_path.contains("/ata") {
+        "sata"
+    } else if real_path.contains("/mmc_host/") {
+        "mmc"
+    } else if real_path.contains("/virtio") {
+        "virtio"
+    } else if real_path.contains("/host") {
+        "scsi"
+    } else {
+        "Unknown"
+    };
+
+    transport.to_string()
+}
@@ -1,11 +1,11 @@
 use core::str;
-use std::{collections::HashMap, env, fs::{self, read_dir, ReadDir}};
+use std::{collections::HashMap, env, fs::{self, read_dir, ReadDir}, io::{Read, Write}, os::unix::net::UnixStream, path::{Path, PathBuf}};
 
 use serde::Deserialize;
-use wayland_client::{protocol::{wl_output::{self, Transform}, wl_registry}, ConnectError, Connection, Dispatch, QueueHandle, WEnum};
+use wayland_client::{event_created_child, protocol::{wl_output::{self, Transform}, wl_registry}, ConnectError, Connection, Dispatch, QueueHandle, WEnum};
 use x11rb::{connection::RequestConnection, protocol::{randr::{self, ConnectionExt, GetCrtcInfoReply, GetOutputInfoReply, GetScreenResourcesCurrentReply, ModeInfo, MonitorInfo, Rotation}, xproto::{self, Screen}}};
 
-use crate::{config_manager::Configuration, formatter::CrabFetchColor, module::Module, util::{self, is_flag_set_u32}, ModuleError};
+use crate::{common_sources::wayland_protocols::{wlr_output_management::{zwlr_output_head_v1::{self, AdaptiveSyncState, ZwlrOutputHeadV1}, zwlr_output_manager_v1::{self, ZwlrOutputManagerV1}, zwlr_output_mode_v1::{self, ZwlrOutputModeV1}}, xdg_output::{zxdg_output_manager_v1::{self, ZxdgOutputManagerV1}, zxdg_output_v1::{self, ZxdgOutputV1}}}, config_manager::Configuration, formatter::CrabFetchColor, module::Module, util::{self, is_flag_set_u32}, ModuleError};
 
 #[derive(Clone)]
 pub struct DisplayInfo {
@@ -17,6 +17,21 @@ pub struct DisplayInfo {
     scale: i32,
     refresh_rate: u16,
     rotation: u16,
+    serial: String,
+    year: Option<u16>,
+    physical_width_mm: Option<u16>,
+    physical_height_mm: Option<u16>,
+    ppi: Option<u32>,
+    hdr: Vec<&'static str>,         // Transfer functions, e.g HDR10, HLG
+    max_luminance: Option<u32>,     // cd/m2
+    vrr_range: Option<(u16, u16)>,
+    primary: bool,
+    // Wayland only
+    description: String,
+    logical_size: Option<(u16, u16)>,
+    fractional_scale: Option<f64>,
+    adaptive_sync: Option<bool>,
+    enabled: bool
 }
 impl DisplayInfo {
     fn calc_rotation(&mut self) {
@@ -25,7 +40,55 @@ impl DisplayInfo {
             (self.width, self.height) = (self.height, self.width);
         }
     }
+    fn apply_edid(&mut self, edid: Edid) {
+        self.make = edid.make;
+        self.model = edid.model;
+        if let Some(serial) = edid.serial {
+            self.serial = serial;
+        }
+        self.year = edid.year;
+        if let Some((width, height)) = edid.size_mm {
+            self.physical_width_mm = Some(width);
+            self.physical_height_mm = Some(height);
+        }
+        self.hdr = edid.hdr;
+        self.max_luminance = edid.max_luminance;
+        self.vrr_range = edid.vrr_range;
+    }
+    // Needs to be done before the resolution is scaled, otherwise we'll get the wrong answer
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn calc_ppi(&mut self) {
+        let Some(diagonal) = self.diagonal_inches() else {
+            return;
+        };
+        let diagonal_pixels: f64 = f64::from(self.width).hypot(f64::from(self.height));
+        if diagonal_pixels == 0.0 {
+            return;
+        }
+        self.ppi = Some((diagonal_pixels / diagonal).round() as u32);
+    }
+    fn diagonal_inches(&self) -> Option<f64> {
+        let diagonal_mm: f64 = f64::from(self.physical_width_mm?).hypot(f64::from(self.physical_height_mm?));
+        if diagonal_mm == 0.0 {
+            return None;
+        }
+        Some(diagonal_mm / 25.4)
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn scale_resolution(&mut self) {
+        // xdg-output tells us exactly what the compositor is using
+        if let Some((width, height)) = self.logical_size {
+            (self.width, self.height) = (width, height);
+            return;
+        }
+        if let Some(scale) = self.fractional_scale.filter(|x| *x > 0.0) {
+            self.width = (f64::from(self.width) / scale).round() as u16;
+            self.height = (f64::from(self.height) / scale).round() as u16;
+            return;
+        }
+        if self.scale <= 0 {
+            return;
+        }
         self.width /= u16::try_from(self.scale).expect("Cannot convert scale to u16");
         self.height /= u16::try_from(self.scale).expect("Cannot convert scale to u16");
     }
@@ -40,6 +103,7 @@ pub struct DisplayConfiguration {
     pub separator: Option<String>,
     pub format: String,
     pub scale_size: bool,
+    pub show_disabled: bool,
 }
 impl Module for DisplayInfo {
     fn new() -> DisplayInfo {
@@ -51,7 +115,21 @@ impl Module for DisplayInfo {
             height: 0,
             scale: 0,
             refresh_rate: 0,
-            rotation: 0
+            rotation: 0,
+            serial: "Unknown".to_string(),
+            year: None,
+            physical_width_mm: None,
+            physical_height_mm: None,
+            ppi: None,
+            hdr: Vec::new(),
+            max_luminance: None,
+            vrr_range: None,
+            primary: false,
+            description: "Unknown".to_string(),
+            logical_size: None,
+            fractional_scale: None,
+            adaptive_sync: None,
+            enabled: true
         }
     }
 
@@ -78,7 +156,21 @@ impl Module for DisplayInfo {
             .replace("{model}", "Unknown")
             .replace("{width}", "Unknown")
             .replace("{height}", "Unknown")
-            .replace("{refresh_rate}", "Unknown");
+            .replace("{refresh_rate}", "Unknown")
+            .replace("{serial}", "Unknown")
+            .replace("{year}", "Unknown")
+            .replace("{physical_width}", "Unknown")
+            .replace("{physical_height}", "Unknown")
+            .replace("{diagonal}", "Unknown")
+            .replace("{ppi}", "Unknown")
+            .replace("{hdr}", "Unknown")
+            .replace("{max_luminance}", "Unknown")
+            .replace("{vrr}", "Unknown")
+            .replace("{primary}", "")
+            .replace("{description}", "Unknown")
+            .replace("{scale}", "Unknown")
+            .replace("{adaptive_sync}", "Unknown")
+            .replace("{enabled}", "Unknown");
 
         Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
     }
@@ -90,6 +182,24 @@ impl Module for DisplayInfo {
             .replace("{width}", &self.width.to_string())
             .replace("{height}", &self.height.to_string())
             .replace("{refresh_rate}", &self.refresh_rate.to_string())
+            .replace("{serial}", &self.serial)
+            .replace("{year}", &self.year.map_or("Unknown".to_string(), |x| x.to_string()))
+            .replace("{physical_width}", &self.physical_width_mm.map_or("Unknown".to_string(), |x| x.to_string()))
+            .replace("{physical_height}", &self.physical_height_mm.map_or("Unknown".to_string(), |x| x.to_string()))
+            .replace("{diagonal}", &self.diagonal_inches().map_or("Unknown".to_string(), |x| format!("{x:.1}")))
+            .replace("{ppi}", &self.ppi.map_or("Unknown".to_string(), |x| x.to_string()))
+            .replace("{hdr}", &if self.hdr.is_empty() {"None".to_string()} else {self.hdr.join(", ")})
+            .replace("{max_luminance}", &self.max_luminance.map_or("Unknown".to_string(), |x| x.to_string()))
+            .replace("{vrr}", &self.vrr_range.map_or("None".to_string(), |(min, max)| format!("{min}-{max}")))
+            .replace("{primary}", if self.primary {"Primary"} else {""})
+            .replace("{description}", &self.description)
+            .replace("{scale}", &self.fractional_scale.map_or(self.scale.to_string(), |x| x.to_string()))
+            .replace("{adaptive_sync}", match self.adaptive_sync {
+                Some(true) => "Enabled",
+                Some(false) => "Disabled",
+                None => "Unknown"
+            })
+            .replace("{enabled}", if self.enabled {"Enabled"} else {"Disabled"})
     }
 
     fn gen_info_flags(format: &str) -> u32 {
@@ -115,6 +225,21 @@ impl Module for DisplayInfo {
         if format.contains("{refresh_rate}") {
             info_flags |= DISPLAYS_INFOFLAG_REFRESH_RATE;
         }
+        if format.contains("{serial}")
+            || format.contains("{year}")
+            || format.contains("{physical_width}")
+            || format.contains("{physical_height}")
+            || format.contains("{diagonal}")
+            || format.contains("{ppi}")
+            || format.contains("{hdr}")
+            || format.contains("{max_luminance}")
+            || format.contains("{vrr}") {
+            info_flags |= DISPLAYS_INFOFLAG_EDID;
+            info_flags |= DISPLAYS_INFOFLAG_DRM_NAME; // DRM name is required for EDID
+        }
+        if format.contains("{primary}") {
+            info_flags |= DISPLAYS_INFOFLAG_PRIMARY;
+        }
 
         info_flags
     }
@@ -126,6 +251,8 @@ const DISPLAYS_INFOFLAG_MODEL: u32 = 4;
 const DISPLAYS_INFOFLAG_WIDTH: u32 = 8;
 const DISPLAYS_INFOFLAG_HEIGHT: u32 = 16;
 const DISPLAYS_INFOFLAG_REFRESH_RATE: u32 = 32;
+const DISPLAYS_INFOFLAG_EDID: u32 = 64;
+const DISPLAYS_INFOFLAG_PRIMARY: u32 = 128;
 
 pub fn get_displays(config: &Configuration) -> Result<Vec<DisplayInfo>, ModuleError> {
     // title is tagged onto the end here to account for the title placeholders
@@ -141,7 +268,8 @@ pub fn get_displays(config: &Configuration) -> Result<Vec<DisplayInfo>, ModuleEr
     } else if env::var("DISPLAY").is_ok() {
         fetch_xorg(info_flags)
     } else {
-        Err(ModuleError::new("Display", "Could not identify desktop session type.".to_string()))
+        // TTYs, SSH etc
+        fetch_drm(Path::new("/sys/class/drm"), info_flags)
     }
 }
 
@@ -180,12 +308,12 @@ fn fetch_xorg(info_flags: u32) -> Result<Vec<DisplayInfo>, ModuleError> {
                 Err(e) => return Err(ModuleError::new("Display", format!("Failed to get atomic name for monitor {}: {e}", monitor.name))),
             };
         }
-        // Find the make/model from the EDID
-        let (mut make, mut model): (String, String) = ("Unknown".to_string(), "Unknown".to_string());
-        if is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_MAKE) || is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_MODEL) {
-            (make, model) = match get_edid_makemodel(&drm_name) {
+        // Find the make/model and everything else from the EDID
+        let mut edid: Option<Edid> = None;
+        if is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_MAKE) || is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_MODEL) || is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_EDID) {
+            edid = match get_edid(&drm_name) {
                 Ok(r) => r,
-                Err(e) => return Err(ModuleError::new("Display", format!("Failed to get make/model for monitor {}: {e}", monitor.name))),
+                Err(e) => return Err(ModuleError::new("Display", format!("Failed to get EDID for monitor {}: {e}", monitor.name))),
             };
         }
 
@@ -227,8 +355,6 @@ fn fetch_xorg(info_flags: u32) -> Result<Vec<DisplayInfo>, ModuleError> {
 
         let mut display = DisplayInfo {
             name: drm_name,
-            make,
-            model,
             width: mode.width,
             height: mode.height,
             scale: 1,
@@ -238,17 +364,36 @@ fn fetch_xorg(info_flags: u32) -> Result<Vec<DisplayInfo>, ModuleError> {
                 Rotation::ROTATE180 => 180,
                 Rotation::ROTATE270 => 270,
                 _ => 0,
-            }
+            },
+            primary: monitor.primary,
+            ..DisplayInfo::new()
         };
+        if let Some(edid) = edid {
+            display.apply_edid(edid);
+        }
         display.calc_rotation();
+        display.calc_ppi();
         displays.push(display);
     }
 
-    displays.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    displays.sort_by_key(|x| x.name.to_lowercase());
     Ok(displays)
 }
 
-fn get_edid_makemodel(drm_name: &str) -> Result<(String, String), String> {
+// Everything we pull out of a display's EDID
+struct Edid {
+    make: String,
+    model: String,
+    serial: Option<String>,
+    year: Option<u16>,
+    size_mm: Option<(u16, u16)>,
+    hdr: Vec<&'static str>,
+    max_luminance: Option<u32>,
+    vrr_range: Option<(u16, u16)>,
+    preferred_mode: Option<(u16, u16, u16)>     // Width, height, refresh rate
+}
+
+fn get_edid(drm_name: &str) -> Result<Option<Edid>, String> {
     // Relative to /sys/class/drm
     // Scans the dir until it finds the first directory ending in that drm name
     // This is because we don't know the GPU device index, and from my (limited) knowledge, no 2
@@ -258,8 +403,6 @@ fn get_edid_makemodel(drm_name: &str) -> Result<(String, String), String> {
         Err(e) => return Err(format!("Unable to open /sys/class/drm: {e}")),
     };
 
-    let mut make: String = "Unknown".to_string();
-    let mut model: String = "Unknown".to_string();
     for x in dir {
         if x.is_err() {
             continue;
@@ -284,60 +427,245 @@ fn get_edid_makemodel(drm_name: &str) -> Result<(String, String), String> {
             continue; // Can happen with VM's, ignore it
         }
 
-        // Thanks to these wonderful sources;
-        // - https://glenwing.github.io/docs/VESA-EEDID-A2.pdf
-        // - https://github.com/tuomas56/edid-rs/tree/master?tab=readme-ov-file
-        //
-        // From what I can tell, manufacturer ID is at byte 8+2
-        // Display model name itself is somewhere buried within a display descriptor, which I have
-        // to go through and find
-
-        let manuid: u16 = (u16::from(edid_bytes[8]) << 8) | u16::from(edid_bytes[9]);
-        // + 64 to convert em to uppercase ascii
-        let char1: char = (((manuid & 0b011111_00000000) >> 10) as u8 + 64) as char;
-        let char2: char = (((manuid & 0b00000011_11100000) >> 5) as u8 + 64) as char;
-        let char3: char = ((manuid & 0b00000000_00011111) as u8 + 64) as char;
-        make = format!("{char1}{char2}{char3}");
-
-        // Now to scower the display descriptors
-        // Byte 48 is where this starts
-        let mut starting_byte: usize = 54;
-        for _ in 0..3 {
-            let is_display: u16 = (u16::from(edid_bytes[starting_byte]) << 8) | u16::from(edid_bytes[starting_byte + 1]);
-            if is_display != 0 {
-                starting_byte += 18;
-                continue;
-            }
+        return Ok(parse_edid(&edid_bytes));
+    }
 
-            // Check the tag
-            let tag: u8 = edid_bytes[starting_byte + 3];
-            if tag != 252 {
-                starting_byte += 18;
-                continue;
+    Ok(None)
+}
+
+// Thanks to these wonderful sources;
+// - https://glenwing.github.io/docs/VESA-EEDID-A2.pdf
+// - https://github.com/tuomas56/edid-rs/tree/master?tab=readme-ov-file
+// - CTA-861 for the extension blocks, which is where HDR and most VRR info lives
+fn parse_edid(edid_bytes: &[u8]) -> Option<Edid> {
+    if edid_bytes.len() < 128 {
+        return None;
+    }
+
+    // From what I can tell, manufacturer ID is at byte 8+2
+    // Display model name itself is somewhere buried within a display descriptor, which I have
+    // to go through and find
+    let manuid: u16 = (u16::from(edid_bytes[8]) << 8) | u16::from(edid_bytes[9]);
+    // + 64 to convert em to uppercase ascii
+    let char1: char = (((manuid & 0b011111_00000000) >> 10) as u8 + 64) as char;
+    let char2: char = (((manuid & 0b00000011_11100000) >> 5) as u8 + 64) as char;
+    let char3: char = ((manuid & 0b00000000_00011111) as u8 + 64) as char;
+    let mut edid: Edid = Edid {
+        make: format!("{char1}{char2}{char3}"),
+        model: "Unknown".to_string(),
+        serial: None,
+        year: None,
+        size_mm: None,
+        hdr: Vec::new(),
+        max_luminance: None,
+        vrr_range: None,
+        preferred_mode: None
+    };
+
+    // Byte 17 is the years since 1990, either of manufacture or the model year if the week is 0xFF
+    if edid_bytes[17] != 0 {
+        edid.year = Some(1990 + u16::from(edid_bytes[17]));
+    }
+
+    // The first detailed timing is the preferred mode, and gives the size in mm where bytes 21/22
+    // only give it in cm
+    if edid_bytes[54] != 0 || edid_bytes[55] != 0 {
+        // Pixel clock is in 10kHz units
+        let pixel_clock: u64 = (u64::from(edid_bytes[54]) | (u64::from(edid_bytes[55]) << 8)) * 10000;
+        let h_active: u16 = u16::from(edid_bytes[56]) | (u16::from(edid_bytes[58] >> 4) << 8);
+        let h_blank: u16 = u16::from(edid_bytes[57]) | (u16::from(edid_bytes[58] & 0x0F) << 8);
+        let v_active: u16 = u16::from(edid_bytes[59]) | (u16::from(edid_bytes[61] >> 4) << 8);
+        let v_blank: u16 = u16::from(edid_bytes[60]) | (u16::from(edid_bytes[61] & 0x0F) << 8);
+        let total: u64 = (u64::from(h_active) + u64::from(h_blank)) * (u64::from(v_active) + u64::from(v_blank));
+        // Rounded to the nearest Hz, as 59.95 and the like are common
+        if let Some(refresh_rate) = (pixel_clock + total / 2).checked_div(total) {
+            edid.preferred_mode = Some((h_active, v_active, u16::try_from(refresh_rate).unwrap_or(0)));
+        }
+
+        let width: u16 = u16::from(edid_bytes[66]) | (u16::from(edid_bytes[68] >> 4) << 8);
+        let height: u16 = u16::from(edid_bytes[67]) | (u16::from(edid_bytes[68] & 0x0F) << 8);
+        if width != 0 && height != 0 {
+            edid.size_mm = Some((width, height));
+        }
+    }
+    if edid.size_mm.is_none() && edid_bytes[21] != 0 && edid_bytes[22] != 0 {
+        edid.size_mm = Some((u16::from(edid_bytes[21]) * 10, u16::from(edid_bytes[22]) * 10));
+    }
+
+    // Now to scower the display descriptors
+    // Byte 54 is where this starts
+    let mut range_limits: Option<(u16, u16)> = None;
+    for starting_byte in [54, 72, 90, 108] {
+        let is_display: u16 = (u16::from(edid_bytes[starting_byte]) << 8) | u16::from(edid_bytes[starting_byte + 1]);
+        if is_display != 0 {
+            continue;
+        }
+
+        // Check the tag
+        // Text is from byte 5+13, padded with newlines/spaces
+        let text: String = edid_bytes[starting_byte + 5..starting_byte + 18].iter()
+            .map(|x| *x as char)
+            .collect::<String>()
+            .trim()
+            .to_string();
+        match edid_bytes[starting_byte + 3] {
+            0xFC => edid.model = text,
+            0xFF => edid.serial = Some(text),
+            0xFD => {
+                // Display range limits, byte 4 says whether to add 255 to either rate
+                let offsets: u8 = edid_bytes[starting_byte + 4];
+                let min_offset: u16 = if offsets & 0b11 == 0b11 {255} else {0};
+                let max_offset: u16 = if offsets & 0b10 == 0b10 {255} else {0};
+                range_limits = Some((u16::from(edid_bytes[starting_byte + 5]) + min_offset, u16::from(edid_bytes[starting_byte + 6]) + max_offset));
+            },
+            _ => {}
+        }
+    }
+
+    if edid.model == "Unknown" {
+        // Now we go for the ID Product Code as a final grasp
+        // This appends the manufacturer on the front as this seems to be the common strategy
+        // for these, tested by my laptop as well as well as this issue's laptop screen
+        // https://github.com/LivacoNew/CrabFetch/issues/21
+        edid.model = format!("{}{:X}", edid.make, u16::from(edid_bytes[10]) | u16::from(edid_bytes[11]) << 8);
+    }
+    if edid.serial.is_none() {
+        let serial: u32 = u32::from_le_bytes([edid_bytes[12], edid_bytes[13], edid_bytes[14], edid_bytes[15]]);
+        if serial != 0 {
+            edid.serial = Some(serial.to_string());
+        }
+    }
+
+    // Extension blocks follow on in 128 byte chunks
+    let mut vendor_vrr: Option<(u16, u16)> = None;
+    for block in edid_bytes[128..].chunks_exact(128) {
+        // CTA-861 blocks only
+        if block[0] != 0x02 {
+            continue;
+        }
+        // Data blocks run from byte 4 until the detailed timings start at byte 2
+        let end: usize = usize::from(block[2]).min(127);
+        let mut pos: usize = 4;
+        while pos < end {
+            let tag: u8 = block[pos] >> 5;
+            let len: usize = usize::from(block[pos] & 0x1F);
+            if pos + len >= end {
+                break;
             }
+            let data: &[u8] = &block[pos + 1..=pos + len];
+            pos += len + 1;
 
-            model = String::new();
-            // Read from byte 5+13 to find the full name
-            for byte in edid_bytes.iter().take(starting_byte + 13).skip(starting_byte + 5) {
-                model.push(*byte as char);
+            match tag {
+                // Vendor specific, identified by their IEEE OUI (stored backwards)
+                3 if data.len() >= 3 => {
+                    let oui: [u8; 3] = [data[2], data[1], data[0]];
+                    match oui {
+                        // AMD's FreeSync block
+                        [0x00, 0x00, 0x1A] if data.len() >= 7 => vendor_vrr = Some((u16::from(data[5]), u16::from(data[6]))),
+                        // HDMI Forum, VRRmin is 6 bits and VRRmax 10
+                        [0xC4, 0x5D, 0xD8] if data.len() >= 10 => {
+                            let min: u16 = u16::from(data[8] & 0x3F);
+                            let max: u16 = (u16::from(data[8] >> 6) << 8) | u16::from(data[9]);
+                            if min != 0 && vendor_vrr.is_none() {
+                                vendor_vrr = Some((min, max));
+                            }
+                        },
+                        _ => {}
+                    }
+                },
+                // Extended tag, 6 being the HDR static metadata block
+                7 if data.len() >= 3 && data[0] == 6 => {
+                    let eotfs: u8 = data[1];
+                    if eotfs & 0b0100 != 0 {
+                        edid.hdr.push("HDR10");
+                    }
+                    if eotfs & 0b1000 != 0 {
+                        edid.hdr.push("HLG");
+                    }
+                    // Desired max luminance, encoded as 50 * 2^(value / 32)
+                    if let Some(value) = data.get(3).filter(|x| **x != 0) {
+                        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                        let nits: u32 = (50.0 * 2.0_f64.powf(f64::from(*value) / 32.0)).round() as u32;
+                        edid.max_luminance = Some(nits);
+                    }
+                },
+                _ => {}
             }
-            model = model.trim().to_string();
+        }
+    }
+
+    // The range limits are there on just about every display, but only mean VRR if the display
+    // says it's continuous frequency (byte 24, bit 0 on EDID 1.4)
+    let continuous: bool = edid_bytes[18] == 1 && edid_bytes[19] >= 4 && edid_bytes[24] & 1 == 1;
+    edid.vrr_range = vendor_vrr.or(if continuous {range_limits} else {None})
+        .filter(|(min, max)| min < max);
+
+    Some(edid)
+}
+
 
-            break;
+// Reads connected monitors straight from the kernel, for when there's no display server to ask
+// Each connector is a directory like card0-DP-1, with a status, it's modes (preferred first) and
+// the EDID
+fn fetch_drm(drm_path: &Path, info_flags: u32) -> Result<Vec<DisplayInfo>, ModuleError> {
+    let dir: ReadDir = match read_dir(drm_path) {
+        Ok(r) => r,
+        Err(e) => return Err(ModuleError::new("Display", format!("Could not identify desktop session type, and can't read from {}: {e}", drm_path.display()))),
+    };
+
+    let mut displays: Vec<DisplayInfo> = Vec::new();
+    for entry in dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let file_name: String = entry.file_name().to_string_lossy().to_string();
+        // Skips the cards themselves, render nodes etc
+        let Some((card, connector)) = file_name.split_once('-') else {
+            continue;
+        };
+        if !card.starts_with("card") {
+            continue;
+        }
+        let path: PathBuf = entry.path();
+        if !util::file_read(&path.join("status")).is_ok_and(|x| x.trim() == "connected") {
+            continue;
         }
 
-        if model == "Unknown" {
-            // Now we go for the ID Product Code as a final grasp
-            // This appends the manufacturer on the front as this seems to be the common strategy
-            // for these, tested by my laptop as well as well as this issue's laptop screen
-            // https://github.com/LivacoNew/CrabFetch/issues/21
-            model = format!("{}{:X}", make, u16::from(edid_bytes[10]) | u16::from(edid_bytes[11]) << 8);
+        let mut display: DisplayInfo = DisplayInfo {
+            name: connector.to_string(),
+            scale: 1,
+            ..DisplayInfo::new()
+        };
+        if let Ok(modes) = util::file_read(&path.join("modes")) {
+            // e.g 1920x1080, or 1920x1080i for interlaced modes
+            if let Some((width, height)) = modes.lines().next().and_then(|x| x.split_once('x')) {
+                display.width = width.parse::<u16>().unwrap_or(0);
+                display.height = height.trim_end_matches(|x: char| !x.is_ascii_digit()).parse::<u16>().unwrap_or(0);
+            }
         }
 
-        break;
+        // sysfs doesn't give us the refresh rate, so it has to come from the EDID's preferred mode
+        if is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_MAKE)
+            || is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_MODEL)
+            || is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_EDID)
+            || is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_REFRESH_RATE) {
+            if let Some(edid) = fs::read(path.join("edid")).ok().and_then(|x| parse_edid(&x)) {
+                if let Some((width, height, refresh_rate)) = edid.preferred_mode {
+                    if display.width == 0 || display.height == 0 {
+                        (display.width, display.height) = (width, height);
+                    }
+                    display.refresh_rate = refresh_rate;
+                }
+                display.apply_edid(edid);
+            }
+        }
+        display.calc_ppi();
+
+        displays.push(display);
     }
 
-    Ok((make, model))
+    displays.sort_by_key(|x| x.name.to_lowercase());
+    Ok(displays)
 }
 
 
@@ -345,31 +673,102 @@ fn get_edid_makemodel(drm_name: &str) -> Result<(String, String), String> {
 // The Wayland Zone
 //
 struct WaylandState {
-    complete: bool, // Tells us whether to break out of the event loop or not
-    num_outputs: u8, // How many outputs are waiting to be processed
-    outputs: HashMap<wl_output::WlOutput, DisplayInfo> // The output data as it stands
+    outputs: HashMap<wl_output::WlOutput, WaylandOutput>,
+    // Optional extras, for compositors that support them
+    xdg_output_manager: Option<ZxdgOutputManagerV1>,
+    xdg_outputs: HashMap<ZxdgOutputV1, wl_output::WlOutput>,  // Mapped back to their wl_output
+    wlr_output_manager: Option<ZwlrOutputManagerV1>,
+    wlr_done: bool,
+    heads: HashMap<ZwlrOutputHeadV1, WaylandHead>,
+    modes: HashMap<ZwlrOutputModeV1, WaylandMode>
+}
+impl WaylandState {
+    // Tells us whether to break out of the event loop or not
+    fn is_complete(&self) -> bool {
+        self.outputs.values().all(|x| x.done && !x.xdg_pending)
+            && (self.wlr_output_manager.is_none() || self.wlr_done)
+    }
+    fn create_xdg_output(&mut self, output: &wl_output::WlOutput, qh: &QueueHandle<WaylandState>) {
+        let Some(manager) = &self.xdg_output_manager else {
+            return;
+        };
+        let xdg_output: ZxdgOutputV1 = manager.get_xdg_output(output, qh, ());
+        self.xdg_outputs.insert(xdg_output, output.clone());
+        if let Some(x) = self.outputs.get_mut(output) {
+            x.xdg_pending = true;
+        }
+    }
+}
+struct WaylandOutput {
+    info: DisplayInfo,
+    done: bool,
+    xdg_pending: bool,
+    logical_size: Option<(u16, u16)>
+}
+// A head is wlr-output-management's version of an output, which includes disabled ones
+struct WaylandHead {
+    name: String,
+    description: String,
+    make: String,
+    model: String,
+    enabled: bool,
+    current_mode: Option<ZwlrOutputModeV1>,
+    modes: Vec<ZwlrOutputModeV1>,
+    scale: Option<f64>,
+    adaptive_sync: Option<bool>
+}
+#[derive(Clone, Copy, Default)]
+struct WaylandMode {
+    width: i32,
+    height: i32,
+    refresh: i32,   // mHz
+    preferred: bool
 }
 impl Dispatch<wl_registry::WlRegistry, ()> for WaylandState {
     fn event(state: &mut Self, reg: &wl_registry::WlRegistry, event: wl_registry::Event, _: &(), _: &Connection, qh: &QueueHandle<WaylandState>,) {
         if let wl_registry::Event::Global {name, interface, version} = event {
-            if interface == "wl_output" {
-                // This is what we're looking for, bind to it
-                reg.bind::<wl_output::WlOutput, _, _>(name, version, qh, ());
-                state.num_outputs += 1;
+            match interface.as_str() {
+                "wl_output" => {
+                    // This is what we're looking for, bind to it
+                    let output: wl_output::WlOutput = reg.bind::<wl_output::WlOutput, _, _>(name, version.min(4), qh, ());
+                    state.outputs.insert(output.clone(), WaylandOutput {
+                        info: DisplayInfo::new(),
+                        // done only exists from v2 onwards
+                        done: version < 2,
+                        xdg_pending: false,
+                        logical_size: None
+                    });
+                    state.create_xdg_output(&output, qh);
+                },
+                "zxdg_output_manager_v1" => {
+                    state.xdg_output_manager = Some(reg.bind::<ZxdgOutputManagerV1, _, _>(name, version.min(3), qh, ()));
+                    // Any outputs we've already seen need catching up
+                    let outputs: Vec<wl_output::WlOutput> = state.outputs.keys().cloned().collect();
+                    for output in outputs {
+                        state.create_xdg_output(&output, qh);
+                    }
+                },
+                "zwlr_output_manager_v1" => {
+                    state.wlr_output_manager = Some(reg.bind::<ZwlrOutputManagerV1, _, _>(name, version.min(4), qh, ()));
+                },
+                _ => {}
             }
         }
     }
 }
 impl Dispatch<wl_output::WlOutput, ()> for WaylandState {
     fn event(state: &mut Self, output: &wl_output::WlOutput, event: wl_output::Event, _: &(), _: &Connection, _qh: &QueueHandle<WaylandState>,) {
-        if !state.outputs.contains_key(output) {
-            state.outputs.insert(output.clone(), DisplayInfo::new());
-        }
+        let Some(wayland_output) = state.outputs.get_mut(output) else {
+            return;
+        };
 
-        let display: &mut DisplayInfo = state.outputs.get_mut(output).unwrap();
+        let display: &mut DisplayInfo = &mut wayland_output.info;
         if let wl_output::Event::Name {name} = &event {
             display.name = name.to_string();
         }
+        if let wl_output::Event::Description {description} = &event {
+            display.description.clone_from(description);
+        }
         if let wl_output::Event::Scale {factor} = &event {
             display.scale = *factor;
         }
@@ -385,20 +784,107 @@ impl Dispatch<wl_output::WlOutput, ()> for WaylandState {
             };
         }
         #[allow(clippy::cast_precision_loss)]
-        if let wl_output::Event::Mode { width, height, refresh, .. } = &event {
-            display.width = width.to_string().parse::<u16>().unwrap_or(0);
-            display.height = height.to_string().parse::<u16>().unwrap_or(0);
-            display.refresh_rate = (*refresh as f32 / 1000.0).round().to_string().parse::<u16>().unwrap_or(0);
-        }
-
-        if !display.name.is_empty() && display.width != 0 && display.height != 0 && display.refresh_rate != 0 {
-            // We're done, release it
-            output.release();
-            if state.num_outputs == 0 {
-                state.complete = true;
-                return;
+        if let wl_output::Event::Mode { flags, width, height, refresh } = &event {
+            // Older compositors send every mode, not just the current one
+            if matches!(flags, WEnum::Value(x) if x.contains(wl_output::Mode::Current)) || display.width == 0 {
+                display.width = width.to_string().parse::<u16>().unwrap_or(0);
+                display.height = height.to_string().parse::<u16>().unwrap_or(0);
+                display.refresh_rate = (*refresh as f32 / 1000.0).round().to_string().parse::<u16>().unwrap_or(0);
             }
-            state.num_outputs -= 1;
+        }
+        if let wl_output::Event::Done = &event {
+            wayland_output.done = true;
+        }
+    }
+}
+impl Dispatch<ZxdgOutputManagerV1, ()> for WaylandState {
+    fn event(_: &mut Self, _: &ZxdgOutputManagerV1, _: zxdg_output_manager_v1::Event, _: &(), _: &Connection, _: &QueueHandle<WaylandState>,) {
+        // No events
+    }
+}
+impl Dispatch<ZxdgOutputV1, ()> for WaylandState {
+    fn event(state: &mut Self, xdg_output: &ZxdgOutputV1, event: zxdg_output_v1::Event, _: &(), _: &Connection, _: &QueueHandle<WaylandState>,) {
+        let Some(wayland_output) = state.xdg_outputs.get(xdg_output).and_then(|x| state.outputs.get_mut(x)) else {
+            return;
+        };
+
+        match event {
+            // This is the size the compositor actually lays things out at, so has fractional
+            // scaling and rotation already applied
+            zxdg_output_v1::Event::LogicalSize {width, height} => {
+                wayland_output.logical_size = Some((u16::try_from(width).unwrap_or(0), u16::try_from(height).unwrap_or(0)));
+                wayland_output.xdg_pending = false;
+            },
+            // wl_output only has these from v4
+            zxdg_output_v1::Event::Name {name} if wayland_output.info.name == "Unknown" => wayland_output.info.name = name,
+            zxdg_output_v1::Event::Description {description} if wayland_output.info.description == "Unknown" => wayland_output.info.description = description,
+            _ => {}
+        }
+    }
+}
+impl Dispatch<ZwlrOutputManagerV1, ()> for WaylandState {
+    fn event(state: &mut Self, _: &ZwlrOutputManagerV1, event: zwlr_output_manager_v1::Event, _: &(), _: &Connection, _: &QueueHandle<WaylandState>,) {
+        match event {
+            zwlr_output_manager_v1::Event::Head {head} => {
+                state.heads.insert(head, WaylandHead {
+                    name: "Unknown".to_string(),
+                    description: "Unknown".to_string(),
+                    make: "Unknown".to_string(),
+                    model: "Unknown".to_string(),
+                    enabled: false,
+                    current_mode: None,
+                    modes: Vec::new(),
+                    scale: None,
+                    adaptive_sync: None
+                });
+            },
+            zwlr_output_manager_v1::Event::Done {..} => state.wlr_done = true,
+            _ => {}
+        }
+    }
+
+    event_created_child!(WaylandState, ZwlrOutputManagerV1, [
+        zwlr_output_manager_v1::EVT_HEAD_OPCODE => (ZwlrOutputHeadV1, ()),
+    ]);
+}
+impl Dispatch<ZwlrOutputHeadV1, ()> for WaylandState {
+    fn event(state: &mut Self, head: &ZwlrOutputHeadV1, event: zwlr_output_head_v1::Event, _: &(), _: &Connection, _: &QueueHandle<WaylandState>,) {
+        if let zwlr_output_head_v1::Event::Mode {mode} = &event {
+            state.modes.insert(mode.clone(), WaylandMode::default());
+        }
+        let Some(wayland_head) = state.heads.get_mut(head) else {
+            return;
+        };
+
+        match event {
+            zwlr_output_head_v1::Event::Name {name} => wayland_head.name = name,
+            zwlr_output_head_v1::Event::Description {description} => wayland_head.description = description,
+            zwlr_output_head_v1::Event::Make {make} => wayland_head.make = make,
+            zwlr_output_head_v1::Event::Model {model} => wayland_head.model = model,
+            zwlr_output_head_v1::Event::Enabled {enabled} => wayland_head.enabled = enabled != 0,
+            zwlr_output_head_v1::Event::Mode {mode} => wayland_head.modes.push(mode),
+            zwlr_output_head_v1::Event::CurrentMode {mode} => wayland_head.current_mode = Some(mode),
+            zwlr_output_head_v1::Event::Scale {scale} => wayland_head.scale = Some(scale),
+            zwlr_output_head_v1::Event::AdaptiveSync {state} => wayland_head.adaptive_sync = Some(state == WEnum::Value(AdaptiveSyncState::Enabled)),
+            _ => {}
+        }
+    }
+
+    event_created_child!(WaylandState, ZwlrOutputHeadV1, [
+        zwlr_output_head_v1::EVT_MODE_OPCODE => (ZwlrOutputModeV1, ()),
+    ]);
+}
+impl Dispatch<ZwlrOutputModeV1, ()> for WaylandState {
+    fn event(state: &mut Self, mode: &ZwlrOutputModeV1, event: zwlr_output_mode_v1::Event, _: &(), _: &Connection, _: &QueueHandle<WaylandState>,) {
+        let Some(wayland_mode) = state.modes.get_mut(mode) else {
+            return;
+        };
+
+        match event {
+            zwlr_output_mode_v1::Event::Size {width, height} => (wayland_mode.width, wayland_mode.height) = (width, height),
+            zwlr_output_mode_v1::Event::Refresh {refresh} => wayland_mode.refresh = refresh,
+            zwlr_output_mode_v1::Event::Preferred => wayland_mode.preferred = true,
+            _ => {}
         }
     }
 }
@@ -416,6 +902,51 @@ fn fetch_wayland(config: &Configuration, info_flags: u32) -> Result<Vec<DisplayI
             return Err(ModuleError::new("Display", format!("Failed to connect to Wayland compositor: {msg}")));
         },
     };
+    let mut displays: Vec<DisplayInfo> = fetch_wayland_outputs(&conn, config.displays.show_disabled)?;
+
+    // Wayland has no concept of a primary output, so the best we can do is ask the compositor which
+    // one is focused
+    let focused_output: Option<String> = if is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_PRIMARY) {
+        get_focused_output()
+    } else {
+        None
+    };
+
+    for x in &mut displays {
+        x.calc_rotation();
+
+        if is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_MAKE) || is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_MODEL) || is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_EDID) {
+            if util::in_wsl() {
+                // WSL has no EDID and the compositor would just return weston's weird virtual display thing
+                x.make = "N/A".to_string();
+                x.model = "N/A".to_string();
+            } else {
+                match get_edid(&x.name) {
+                    Ok(r) => if let Some(edid) = r {
+                        x.apply_edid(edid);
+                    },
+                    Err(e) => return Err(ModuleError::new("Display", format!("Cannot parse EDID: {e}")))
+                }
+            }
+        }
+        x.calc_ppi();
+        x.primary = focused_output.as_ref().is_some_and(|focused| *focused == x.name);
+
+        if config.displays.scale_size {
+            x.scale_resolution();
+        }
+        if is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_DRM_NAME) && util::in_wsl() {
+            // WSL also doesn't have any DRM name
+            x.name = "N/A".to_string();
+        }
+    }
+
+    displays.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    Ok(displays)
+}
+
+// Kept separate from fetch_wayland so it can be pointed at any connection
+fn fetch_wayland_outputs(conn: &Connection, show_disabled: bool) -> Result<Vec<DisplayInfo>, ModuleError> {
     let display = conn.display();
 
     let mut event_queue = conn.new_event_queue();
@@ -423,13 +954,18 @@ fn fetch_wayland(config: &Configuration, info_flags: u32) -> Result<Vec<DisplayI
 
     let _registry = display.get_registry(&qh, ());
     let mut data: WaylandState = WaylandState {
-        complete: false,
-        num_outputs: 0,
-        outputs: HashMap::new()
+        outputs: HashMap::new(),
+        xdg_output_manager: None,
+        xdg_outputs: HashMap::new(),
+        wlr_output_manager: None,
+        wlr_done: false,
+        heads: HashMap::new(),
+        modes: HashMap::new()
     };
 
+    // The first roundtrip only gets us the globals, so there's always at least 2
     let mut loops: u16 = 0;
-    while !data.complete {
+    while loops < 2 || !data.is_complete() {
         match event_queue.roundtrip(&mut data) {
             Ok(r) => r,
             Err(e) => return Err(ModuleError::new("Display", format!("Compositor roundtrip returned error: {e}")))
@@ -440,34 +976,311 @@ fn fetch_wayland(config: &Configuration, info_flags: u32) -> Result<Vec<DisplayI
         }
     }
 
-    let mut displays: Vec<DisplayInfo> = data.outputs.into_iter()
-        .map(|x| x.1.clone())
-        .collect();
-
-    for x in &mut displays {
-        x.calc_rotation();
-        if config.displays.scale_size {
-            x.scale_resolution();
+    let mut displays: Vec<DisplayInfo> = Vec::new();
+    for output in data.outputs.into_values() {
+        let mut display: DisplayInfo = output.info;
+        display.logical_size = output.logical_size;
+        if let Some(head) = data.heads.values().find(|x| x.name == display.name) {
+            display.fractional_scale = head.scale;
+            display.adaptive_sync = head.adaptive_sync;
+            if display.description == "Unknown" {
+                display.description.clone_from(&head.description);
+            }
         }
+        displays.push(display);
+    }
 
-        if is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_MAKE) || is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_MODEL) {
-            if util::in_wsl() {
-                // WSL has no EDID and the compositor would just return weston's weird virtual display thing
-                x.make = "N/A".to_string();
-                x.model = "N/A".to_string();
-            } else {
-                (x.make, x.model) = match get_edid_makemodel(&x.name) {
-                    Ok(r) => r,
-                    Err(e) => return Err(ModuleError::new("Display", format!("Cannot parse EDID: {e}")))
-                };
+    // Disabled outputs don't get a wl_output, so these can only come from wlr-output-management
+    if show_disabled {
+        for head in data.heads.values().filter(|x| !x.enabled) {
+            let mut display: DisplayInfo = DisplayInfo {
+                name: head.name.clone(),
+                description: head.description.clone(),
+                make: head.make.clone(),
+                model: head.model.clone(),
+                scale: 1,
+                adaptive_sync: head.adaptive_sync,
+                enabled: false,
+                ..DisplayInfo::new()
+            };
+            // No current mode while it's disabled, so go with what it'd prefer
+            let mode: Option<WaylandMode> = head.modes.iter()
+                .filter_map(|x| data.modes.get(x))
+                .find(|x| x.preferred)
+                .or_else(|| head.current_mode.as_ref().and_then(|x| data.modes.get(x)))
+                .copied();
+            if let Some(mode) = mode {
+                display.width = u16::try_from(mode.width).unwrap_or(0);
+                display.height = u16::try_from(mode.height).unwrap_or(0);
+                display.refresh_rate = u16::try_from((mode.refresh + 500) / 1000).unwrap_or(0);
             }
-        }
-        if is_flag_set_u32(info_flags, DISPLAYS_INFOFLAG_DRM_NAME) && util::in_wsl() {
-            // WSL also doesn't have any DRM name
-            x.name = "N/A".to_string();
+            displays.push(display);
         }
     }
 
-    displays.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
     Ok(displays)
 }
+
+// Only Hyprland and Sway tell us this, through their IPC sockets
+fn get_focused_output() -> Option<String> {
+    let outputs: serde_json::Value = if let Ok(signature) = env::var("HYPRLAND_INSTANCE_SIGNATURE") {
+        // Moved into the runtime dir in 0.40
+        let socket_path: PathBuf = util::find_first_pathbuf_exists(vec![
+            PathBuf::from(env::var("XDG_RUNTIME_DIR").unwrap_or_default()).join("hypr").join(&signature).join(".socket.sock"),
+            PathBuf::from("/tmp/hypr").join(&signature).join(".socket.sock")
+        ])?;
+        let mut stream: UnixStream = UnixStream::connect(socket_path).ok()?;
+        stream.write_all(b"j/monitors").ok()?;
+        let mut response: Vec<u8> = Vec::new();
+        stream.read_to_end(&mut response).ok()?;
+        serde_json::from_slice(&response).ok()?
+    } else if let Ok(socket_path) = env::var("SWAYSOCK") {
+        // i3's IPC format, the magic string followed by the payload length and type
+        // https://man.archlinux.org/man/sway-ipc.7.en
+        const GET_OUTPUTS: u32 = 3;
+        let mut stream: UnixStream = UnixStream::connect(socket_path).ok()?;
+        let mut message: Vec<u8> = b"i3-ipc".to_vec();
+        message.extend_from_slice(&0_u32.to_ne_bytes());
+        message.extend_from_slice(&GET_OUTPUTS.to_ne_bytes());
+        stream.write_all(&message).ok()?;
+
+        let mut header: [u8; 14] = [0; 14];
+        stream.read_exact(&mut header).ok()?;
+        let length: u32 = u32::from_ne_bytes(header[6..10].try_into().ok()?);
+        let mut response: Vec<u8> = vec![0; length as usize];
+        stream.read_exact(&mut response).ok()?;
+        serde_json::from_slice(&response).ok()?
+    } else {
+        return None;
+    };
+
+    outputs.as_array()?.iter()
+        .find(|x| x["focused"].as_bool() == Some(true))
+        .and_then(|x| Some(x["name"].as_str()?.to_string()))
+}
+
+mod tests {
+    // A 27" 1440p 60Hz monitor, with HDR
+    #[allow(dead_code)]
+    fn make_edid() -> Vec<u8> {
+        let mut edid: Vec<u8> = vec![0; 256];
+        edid[0..8].copy_from_slice(&[0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00]);
+        edid[8..10].copy_from_slice(&[0x10, 0xAC]);             // DEL
+        edid[12..16].copy_from_slice(&1234_u32.to_le_bytes());
+        edid[16..18].copy_from_slice(&[12, 33]);                // Week 12 of 2023
+        edid[18..20].copy_from_slice(&[1, 4]);
+        edid[21..23].copy_from_slice(&[60, 34]);
+        edid[24] = 0b0000_0001;                                 // Continuous frequency
+        // Detailed timing, 2560x1440 with 2720x1481 total at 241.71MHz, and 597x336mm
+        edid[54..62].copy_from_slice(&[0x6B, 0x5E, 0x00, 0xA0, 0xA0, 0xA0, 0x29, 0x50]);
+        edid[66..69].copy_from_slice(&[0x55, 0x50, 0x21]);
+        // Range limits, 48-144Hz
+        edid[72..81].copy_from_slice(&[0, 0, 0, 0xFD, 0, 48, 144, 30, 160]);
+        // Name
+        edid[90..95].copy_from_slice(&[0, 0, 0, 0xFC, 0]);
+        edid[95..108].copy_from_slice(b"DELL S2721DG\n");
+        // Serial string takes priority over the number
+        edid[108..113].copy_from_slice(&[0, 0, 0, 0xFF, 0]);
+        edid[113..126].copy_from_slice(b"ABC123\n      ");
+        edid[126] = 1;
+
+        // CTA extension, with a HDR static metadata block
+        edid[128..132].copy_from_slice(&[0x02, 0x03, 10, 0]);
+        edid[132..138].copy_from_slice(&[0xE5, 0x06, 0b0000_1101, 0x01, 0x62, 0x00]);
+        edid
+    }
+
+    #[test]
+    fn test_parse_edid() {
+        use crate::modules::displays::parse_edid;
+
+        let mut edid: Vec<u8> = make_edid();
+        let result = parse_edid(&edid).unwrap();
+        assert_eq!(result.make, "DEL");
+        assert_eq!(result.model, "DELL S2721DG");
+        assert_eq!(result.serial.as_deref(), Some("ABC123"));
+        assert_eq!(result.year, Some(2023));
+        assert_eq!(result.size_mm, Some((597, 336)));
+        assert_eq!(result.hdr, vec!["HDR10", "HLG"]);
+        assert_eq!(result.max_luminance, Some(418));
+        assert_eq!(result.vrr_range, Some((48, 144)));
+        assert_eq!(result.preferred_mode, Some((2560, 1440, 60)));
+
+        // Range limits on their own shouldn't count as VRR, and FreeSync's block should win out
+        edid[24] = 0;
+        assert_eq!(parse_edid(&edid).unwrap().vrr_range, None);
+        edid[130] = 18;
+        edid[138..142].copy_from_slice(&[0x67, 0x1A, 0x00, 0x00]);
+        edid[142..145].copy_from_slice(&[0x01, 0x01, 40]);
+        edid[145] = 165;
+        assert_eq!(parse_edid(&edid).unwrap().vrr_range, Some((40, 165)));
+
+        // HDMI Forum's block in place of FreeSync's, 48-400Hz (0x30 is VRRmin, 0x40 and 0x90 VRRmax)
+        edid[130] = 21;
+        edid[138..141].copy_from_slice(&[0x6A, 0xD8, 0x5D]);
+        edid[141..148].copy_from_slice(&[0xC4, 0x01, 0x78, 0x80, 0x00, 0x00, 0x30 | 0x40]);
+        edid[148] = 0x90;
+        assert_eq!(parse_edid(&edid).unwrap().vrr_range, Some((48, 400)));
+
+        assert!(parse_edid(&edid[..100]).is_none());
+    }
+
+    #[test]
+    fn test_drm_displays() {
+        use crate::{module::Module, modules::displays::{fetch_drm, DisplayInfo}};
+
+        let root: std::path::PathBuf = std::env::temp_dir().join(format!("crabfetch-drm-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        for (connector, status, modes, edid) in [
+            ("card1-DP-1", "connected", "2560x1440\n1920x1080\n", make_edid()),
+            ("card1-HDMI-A-1", "disconnected", "", Vec::new()),
+            ("card1-eDP-1", "connected", "", make_edid()),
+        ] {
+            let path: std::path::PathBuf = root.join(connector);
+            std::fs::create_dir_all(&path).unwrap();
+            std::fs::write(path.join("status"), format!("{status}\n")).unwrap();
+            std::fs::write(path.join("modes"), modes).unwrap();
+            std::fs::write(path.join("edid"), edid).unwrap();
+        }
+        std::fs::create_dir_all(root.join("card1")).unwrap();
+        std::fs::create_dir_all(root.join("renderD128")).unwrap();
+
+        let info_flags: u32 = DisplayInfo::gen_info_flags("{make} {model} {width}x{height} @ {refresh_rate}Hz {ppi}");
+        let displays: Vec<DisplayInfo> = fetch_drm(&root, info_flags).unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(displays.len(), 2);
+        assert_eq!(displays[0].name, "DP-1");
+        assert_eq!(displays[0].model, "DELL S2721DG");
+        assert_eq!((displays[0].width, displays[0].height, displays[0].refresh_rate), (2560, 1440, 60));
+        assert_eq!(displays[0].ppi, Some(109));
+        // No modes, so falls back to the EDID's
+        assert_eq!(displays[1].name, "eDP-1");
+        assert_eq!((displays[1].width, displays[1].height), (2560, 1440));
+    }
+
+    // A bare-bones compositor, speaking just enough of the wire protocol to answer what
+    // fetch_wayland_outputs asks for
+    // https://wayland.freedesktop.org/docs/html/ch04.html#sect-Protocol-Wire-Format
+    #[allow(dead_code)]
+    fn mock_compositor(mut stream: std::os::unix::net::UnixStream) {
+        use std::{collections::HashMap, io::{Read, Write}};
+
+        fn string(value: &str) -> Vec<u8> {
+            let mut bytes: Vec<u8> = u32::try_from(value.len() + 1).unwrap().to_ne_bytes().to_vec();
+            bytes.extend_from_slice(value.as_bytes());
+            bytes.resize(4 + (value.len() + 4) / 4 * 4, 0);
+            bytes
+        }
+        fn int(value: i32) -> Vec<u8> {
+            value.to_ne_bytes().to_vec()
+        }
+        fn send(stream: &mut std::os::unix::net::UnixStream, object: u32, opcode: u16, args: &[Vec<u8>]) {
+            let payload: Vec<u8> = args.concat();
+            let mut message: Vec<u8> = object.to_ne_bytes().to_vec();
+            message.extend_from_slice(&((u32::try_from(payload.len()).unwrap() + 8) << 16 | u32::from(opcode)).to_ne_bytes());
+            message.extend_from_slice(&payload);
+            stream.write_all(&message).unwrap();
+        }
+        let read_u32 = |body: &[u8], pos: usize| u32::from_ne_bytes(body[pos..pos + 4].try_into().unwrap());
+
+        let mut objects: HashMap<u32, String> = HashMap::from([(1, "wl_display".to_string())]);
+        let mut header: [u8; 8] = [0; 8];
+        while stream.read_exact(&mut header).is_ok() {
+            let object: u32 = read_u32(&header, 0);
+            let size: usize = (read_u32(&header, 4) >> 16) as usize;
+            let opcode: u32 = read_u32(&header, 4) & 0xFFFF;
+            let mut body: Vec<u8> = vec![0; size - 8];
+            stream.read_exact(&mut body).unwrap();
+
+            match (objects.get(&object).map(String::as_str), opcode) {
+                // wl_display.sync
+                (Some("wl_display"), 0) => {
+                    let callback: u32 = read_u32(&body, 0);
+                    send(&mut stream, callback, 0, &[int(0)]);
+                    send(&mut stream, 1, 1, &[callback.to_ne_bytes().to_vec()]);
+                },
+                // wl_display.get_registry
+                (Some("wl_display"), 1) => {
+                    let registry: u32 = read_u32(&body, 0);
+                    objects.insert(registry, "wl_registry".to_string());
+                    for (name, interface, version) in [(1, "wl_output", 4), (2, "zxdg_output_manager_v1", 3), (3, "zwlr_output_manager_v1", 4)] {
+                        send(&mut stream, registry, 0, &[int(name), string(interface), int(version)]);
+                    }
+                },
+                // wl_registry.bind
+                (Some("wl_registry"), 0) => {
+                    let interface_len: usize = read_u32(&body, 4) as usize;
+                    let interface: String = String::from_utf8(body[8..8 + interface_len - 1].to_vec()).unwrap();
+                    let id: u32 = read_u32(&body, 8 + interface_len.div_ceil(4) * 4 + 4);
+                    objects.insert(id, interface.clone());
+                    if interface == "wl_output" {
+                        send(&mut stream, id, 0, &[int(0), int(0), int(300), int(190), int(0), string("Make"), string("Model"), int(0)]);
+                        send(&mut stream, id, 1, &[int(3), int(2880), int(1800), int(120_000)]);
+                        send(&mut stream, id, 3, &[int(2)]);
+                        send(&mut stream, id, 4, &[string("eDP-1")]);
+                        send(&mut stream, id, 2, &[]);
+                    }
+                    if interface == "zwlr_output_manager_v1" {
+                        // Server created objects get IDs from 0xFF000000 up
+                        // Name, enabled, mode, scale (in 24.8 fixed point), adaptive sync
+                        for (index, (name, enabled, mode, scale, adaptive_sync)) in [("eDP-1", 1, (2880, 1800, 120_000), 384, 1), ("HDMI-A-1", 0, (1920, 1080, 60_000), 256, 0)].into_iter().enumerate() {
+                            let head: u32 = 0xFF00_0000 + u32::try_from(index).unwrap() * 2;
+                            let mode_id: u32 = head + 1;
+                            send(&mut stream, id, 0, &[head.to_ne_bytes().to_vec()]);
+                            send(&mut stream, head, 0, &[string(name)]);
+                            send(&mut stream, head, 3, &[mode_id.to_ne_bytes().to_vec()]);
+                            send(&mut stream, mode_id, 0, &[int(mode.0), int(mode.1)]);
+                            send(&mut stream, mode_id, 1, &[int(mode.2)]);
+                            send(&mut stream, mode_id, 2, &[]);
+                            send(&mut stream, head, 4, &[int(enabled)]);
+                            if enabled == 1 {
+                                send(&mut stream, head, 5, &[mode_id.to_ne_bytes().to_vec()]);
+                                send(&mut stream, head, 8, &[int(scale)]);
+                            }
+                            send(&mut stream, head, 13, &[int(adaptive_sync)]);
+                        }
+                        send(&mut stream, id, 1, &[int(1)]);
+                    }
+                },
+                // zxdg_output_manager_v1.get_xdg_output
+                (Some("zxdg_output_manager_v1"), 1) => {
+                    let id: u32 = read_u32(&body, 0);
+                    let output: u32 = read_u32(&body, 4);
+                    send(&mut stream, id, 1, &[int(1920), int(1200)]);
+                    send(&mut stream, id, 4, &[string("Built-in display")]);
+                    send(&mut stream, output, 2, &[]);
+                },
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn test_wayland_outputs() {
+        use crate::modules::displays::{fetch_wayland_outputs, DisplayInfo};
+
+        let (client, server) = std::os::unix::net::UnixStream::pair().unwrap();
+        let compositor = std::thread::spawn(move || mock_compositor(server));
+        let conn: wayland_client::Connection = wayland_client::Connection::from_socket(client).unwrap();
+        let mut displays: Vec<DisplayInfo> = fetch_wayland_outputs(&conn, true).unwrap();
+        drop(conn);
+        compositor.join().unwrap();
+
+        assert_eq!(displays.len(), 2);
+        let display: &mut DisplayInfo = &mut displays[0];
+        assert_eq!(display.name, "eDP-1");
+        assert_eq!((display.width, display.height, display.refresh_rate), (2880, 1800, 120));
+        assert_eq!(display.description, "Built-in display");
+        assert_eq!(display.fractional_scale, Some(1.5));
+        assert_eq!(display.adaptive_sync, Some(true));
+        assert!(display.enabled);
+        display.scale_resolution();
+        assert_eq!((display.width, display.height), (1920, 1200));
+
+        let display: &DisplayInfo = &displays[1];
+        assert_eq!(display.name, "HDMI-A-1");
+        assert_eq!((display.width, display.height, display.refresh_rate), (1920, 1080, 60));
+        assert!(!display.enabled);
+    }
+}
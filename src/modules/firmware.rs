@@ -0,0 +1,157 @@
+use std::{fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::{common_sources::dmi, config_manager::Configuration, formatter::CrabFetchColor, module::Module, util::is_flag_set_u32, ModuleError};
+
+pub struct FirmwareInfo {
+    vendor: String,
+    version: String,
+    date: String,
+    boot_mode: String,  // UEFI / Legacy
+    secure_boot: String
+}
+#[derive(Deserialize)]
+pub struct FirmwareConfiguration {
+    pub title: String,
+    pub title_color: Option<CrabFetchColor>,
+    pub title_bold: Option<bool>,
+    pub title_italic: Option<bool>,
+    pub separator: Option<String>,
+    pub format: String
+}
+impl Module for FirmwareInfo {
+    fn new() -> FirmwareInfo {
+        FirmwareInfo {
+            vendor: "Unknown".to_string(),
+            version: "Unknown".to_string(),
+            date: "Unknown".to_string(),
+            boot_mode: "Unknown".to_string(),
+            secure_boot: "Unknown".to_string()
+        }
+    }
+
+    fn style(&self, config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.firmware.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.firmware.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.firmware.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.firmware.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = self.replace_placeholders(&config.firmware.title, config);
+        let value: String = self.replace_color_placeholders(&self.replace_placeholders(&config.firmware.format, config), config);
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value)
+    }
+    fn unknown_output(config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.firmware.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.firmware.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.firmware.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.firmware.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = config.firmware.title
+            .replace("{vendor}", "Unknown")
+            .replace("{version}", "Unknown")
+            .replace("{date}", "Unknown")
+            .replace("{boot_mode}", "Unknown")
+            .replace("{secure_boot}", "Unknown");
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
+    }
+
+    fn replace_placeholders(&self, text: &str, _: &Configuration) -> String {
+        text.replace("{vendor}", &self.vendor)
+            .replace("{version}", &self.version)
+            .replace("{date}", &self.date)
+            .replace("{boot_mode}", &self.boot_mode)
+            .replace("{secure_boot}", &self.secure_boot)
+    }
+
+    fn gen_info_flags(format: &str) -> u32 {
+        let mut info_flags: u32 = 0;
+
+        if format.contains("{vendor}") {
+            info_flags |= FIRMWARE_INFOFLAG_VENDOR;
+        }
+        if format.contains("{version}") {
+            info_flags |= FIRMWARE_INFOFLAG_VERSION;
+        }
+        if format.contains("{date}") {
+            info_flags |= FIRMWARE_INFOFLAG_DATE;
+        }
+        if format.contains("{boot_mode}") {
+            info_flags |= FIRMWARE_INFOFLAG_BOOT_MODE;
+        }
+        if format.contains("{secure_boot}") {
+            info_flags |= FIRMWARE_INFOFLAG_SECURE_BOOT;
+        }
+
+        info_flags
+    }
+}
+
+const FIRMWARE_INFOFLAG_VENDOR: u32 = 1;
+const FIRMWARE_INFOFLAG_VERSION: u32 = 2;
+const FIRMWARE_INFOFLAG_DATE: u32 = 4;
+const FIRMWARE_INFOFLAG_BOOT_MODE: u32 = 8;
+const FIRMWARE_INFOFLAG_SECURE_BOOT: u32 = 16;
+
+pub fn get_firmware(config: &Configuration) -> Result<FirmwareInfo, ModuleError> {
+    let mut firmware: FirmwareInfo = FirmwareInfo::new();
+    // title is tagged onto the end here to account for the title placeholders
+    let info_flags: u32 = FirmwareInfo::gen_info_flags(&format!("{}{}", config.firmware.format, config.firmware.title));
+
+    if is_flag_set_u32(info_flags, FIRMWARE_INFOFLAG_VENDOR) {
+        firmware.vendor = dmi::read_value("bios_vendor").map_err(|e| ModuleError::new("Firmware", e))?;
+    }
+    if is_flag_set_u32(info_flags, FIRMWARE_INFOFLAG_VERSION) {
+        firmware.version = dmi::read_value("bios_version").map_err(|e| ModuleError::new("Firmware", e))?;
+    }
+    if is_flag_set_u32(info_flags, FIRMWARE_INFOFLAG_DATE) {
+        firmware.date = dmi::read_value("bios_date").map_err(|e| ModuleError::new("Firmware", e))?;
+    }
+
+    // The kernel only creates this when it was booted by UEFI
+    let uefi: bool = Path::new("/sys/firmware/efi").exists();
+    if is_flag_set_u32(info_flags, FIRMWARE_INFOFLAG_BOOT_MODE) {
+        firmware.boot_mode = if uefi {"UEFI"} else {"Legacy"}.to_string();
+    }
+    if is_flag_set_u32(info_flags, FIRMWARE_INFOFLAG_SECURE_BOOT) {
+        firmware.secure_boot = if uefi {
+            match read_secure_boot() {
+                Some(true) => "Enabled",
+                Some(false) => "Disabled",
+                None => "Unknown",
+            }
+        } else {
+            "N/A" // Secure boot is a UEFI only feature
+        }.to_string();
+    }
+
+    Ok(firmware)
+}
+
+// The SecureBoot variable is a single byte; 1 for enabled, 0 for disabled
+// efivarfs prefixes every variable with 4 bytes of attributes, while the older sysfs interface
+// gives the raw data in it's own file
+fn read_secure_boot() -> Option<bool> {
+    for (dir, data_offset) in [("/sys/firmware/efi/efivars", 4), ("/sys/firmware/efi/vars", 0)] {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if !entry.file_name().to_string_lossy().starts_with("SecureBoot-") {
+                continue;
+            }
+
+            let path = if data_offset == 0 {
+                entry.path().join("data")
+            } else {
+                entry.path()
+            };
+            let data: Vec<u8> = fs::read(path).ok()?;
+            return data.get(data_offset).map(|x| *x == 1);
+        }
+    }
+
+    None
+}
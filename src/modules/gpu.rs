@@ -1,16 +1,25 @@
 use core::str;
-use std::{fs::{self, DirEntry, File, ReadDir}, io::{BufRead, BufReader}, path::Path};
+use std::{fs::{self, DirEntry, ReadDir}, path::{Path, PathBuf}};
 
 use serde::Deserialize;
 
-use crate::{config_manager::Configuration, formatter::{self, CrabFetchColor}, module::Module, util::{self, is_flag_set_u32}, ModuleError};
+use crate::{common_sources::{hwids, ids_index::{IdsFormat, IdsIndex}, pci}, config_manager::Configuration, formatter::{self, CrabFetchColor}, module::Module, util::{self, is_flag_set_u32}, ModuleError};
 
 #[derive(Clone)]
 pub struct GPUInfo {
     index: Option<u8>,
     vendor: String,
     model: String,
+    vendor_id: String,
+    device_id: String,
     vram_mb: u32,
+    vram_used_mb: Option<u32>,
+    driver: String,
+    slot: String,               // 0000:03:00.0
+    gpu_type: String,           // Integrated / Discrete
+    clock_mhz: Option<u32>,
+    max_clock_mhz: Option<u32>,
+    busy_percent: Option<u32>
 }
 #[derive(Deserialize)]
 pub struct GPUConfiguration {
@@ -33,7 +42,16 @@ impl Module for GPUInfo {
             index: None,
             vendor: "Unknown".to_string(),
             model: "Unknown".to_string(),
-            vram_mb: 0
+            vendor_id: "Unknown".to_string(),
+            device_id: "Unknown".to_string(),
+            vram_mb: 0,
+            vram_used_mb: None,
+            driver: "Unknown".to_string(),
+            slot: "Unknown".to_string(),
+            gpu_type: "Unknown".to_string(),
+            clock_mhz: None,
+            max_clock_mhz: None,
+            busy_percent: None
         }
     }
 
@@ -58,7 +76,16 @@ impl Module for GPUInfo {
         let title: String = config.gpu.title
             .replace("{vendor}", "Unknown")
             .replace("{model}", "Unknown")
+            .replace("{vendor_id}", "Unknown")
+            .replace("{device_id}", "Unknown")
             .replace("{vram}", "Unknown")
+            .replace("{vram_used}", "Unknown")
+            .replace("{driver}", "Unknown")
+            .replace("{slot}", "Unknown")
+            .replace("{type}", "Unknown")
+            .replace("{clock}", "Unknown")
+            .replace("{max_clock}", "Unknown")
+            .replace("{busy}", "Unknown")
             .replace("{index}", "0").to_string();
 
         Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
@@ -69,7 +96,16 @@ impl Module for GPUInfo {
 
         text.replace("{vendor}", &self.vendor)
             .replace("{model}", &self.model)
+            .replace("{vendor_id}", &self.vendor_id)
+            .replace("{device_id}", &self.device_id)
             .replace("{vram}", &formatter::auto_format_bytes(u64::from(self.vram_mb * 1000), use_ibis, 0))
+            .replace("{vram_used}", &self.vram_used_mb.map_or("Unknown".to_string(), |x| formatter::auto_format_bytes(u64::from(x) * 1000, use_ibis, 0)))
+            .replace("{driver}", &self.driver)
+            .replace("{slot}", &self.slot)
+            .replace("{type}", &self.gpu_type)
+            .replace("{clock}", &self.clock_mhz.map_or("Unknown".to_string(), |x| format!("{x} MHz")))
+            .replace("{max_clock}", &self.max_clock_mhz.map_or("Unknown".to_string(), |x| format!("{x} MHz")))
+            .replace("{busy}", &self.busy_percent.map_or("Unknown".to_string(), |x| format!("{x}%")))
             .replace("{index}", &self.index.unwrap_or(0).to_string())
     }
 
@@ -88,6 +124,21 @@ impl Module for GPUInfo {
         if format.contains("{vram}") {
             info_flags |= GPU_INFOFLAG_VRAM;
         }
+        if format.contains("{vram_used}") {
+            info_flags |= GPU_INFOFLAG_VRAM_USED;
+        }
+        if format.contains("{driver}") {
+            info_flags |= GPU_INFOFLAG_DRIVER;
+        }
+        if format.contains("{type}") {
+            info_flags |= GPU_INFOFLAG_TYPE;
+        }
+        if format.contains("{clock}") || format.contains("{max_clock}") {
+            info_flags |= GPU_INFOFLAG_CLOCKS;
+        }
+        if format.contains("{busy}") {
+            info_flags |= GPU_INFOFLAG_BUSY;
+        }
 
         info_flags
     }
@@ -101,18 +152,36 @@ impl GPUInfo {
 const GPU_INFOFLAG_VENDOR: u32 = 1;
 const GPU_INFOFLAG_MODEL: u32 = 2;
 const GPU_INFOFLAG_VRAM: u32 = 4;
+const GPU_INFOFLAG_VRAM_USED: u32 = 8;
+const GPU_INFOFLAG_DRIVER: u32 = 16;
+const GPU_INFOFLAG_TYPE: u32 = 32;
+const GPU_INFOFLAG_CLOCKS: u32 = 64;
+const GPU_INFOFLAG_BUSY: u32 = 128;
 
 pub fn get_gpus(config: &Configuration) -> Result<Vec<GPUInfo>, ModuleError> {
     let mut gpus: Vec<GPUInfo> = Vec::new();
     let info_flags: u32 = GPUInfo::gen_info_flags(&config.gpu.format);
+    // Only opened once, rather than for every GPU
+    let needs_names: bool = is_flag_set_u32(info_flags, GPU_INFOFLAG_MODEL) || is_flag_set_u32(info_flags, GPU_INFOFLAG_VENDOR);
+    let ids: Option<IdsIndex> = if needs_names {
+        hwids::find_pci_ids(config.pci_ids_path.as_deref()).and_then(|x| hwids::open_ids(&x).ok())
+    } else {
+        None
+    };
+    // Not everyone has libdrm installed, so this just falls back to pci.ids if it's not there
+    let amd_ids: Option<IdsIndex> = if needs_names && config.gpu.amd_accuracy {
+        open_amd_ids().ok()
+    } else {
+        None
+    };
 
     if config.gpu.detect_through_driver {
-        match fill_from_drivers(&mut gpus, config.gpu.amd_accuracy, config.gpu.ignore_disabled_gpus, info_flags) {
+        match fill_from_drivers(&mut gpus, ids.as_ref(), amd_ids.as_ref(), config.gpu.ignore_disabled_gpus, info_flags) {
             Ok(_) => {},
             Err(e) => return Err(e)
         }
     } else {
-        match fill_from_pcisysfile(&mut gpus, config.gpu.amd_accuracy, config.gpu.ignore_disabled_gpus, info_flags) {
+        match fill_from_pcisysfile(&mut gpus, ids.as_ref(), amd_ids.as_ref(), config.gpu.ignore_disabled_gpus, info_flags) {
             Ok(_) => {},
             Err(e) => return Err(e)
         }
@@ -133,7 +202,7 @@ const KNOWN_GPU_DRIVERS: &[&str] = &[
     "i915",
     "xe"
 ];
-fn fill_from_drivers(gpus: &mut Vec<GPUInfo>, amd_accuracy: bool, ignore_disabled: bool, info_flags: u32) -> Result<(), ModuleError> {
+fn fill_from_drivers(gpus: &mut Vec<GPUInfo>, ids: Option<&IdsIndex>, amd_ids: Option<&IdsIndex>, ignore_disabled: bool, info_flags: u32) -> Result<(), ModuleError> {
     let dir: ReadDir = match fs::read_dir("/sys/bus/pci/drivers") {
         Ok(r) => r,
         Err(e) => return Err(ModuleError::new("GPU", format!("Can't read from /sys/bus/pci/drivers: {e}")))
@@ -171,7 +240,7 @@ fn fill_from_drivers(gpus: &mut Vec<GPUInfo>, amd_accuracy: bool, ignore_disable
 
             // So, in *theory*, this is a GPU 
             // Let's scan it :)
-            let Ok(Some(mut gpu)) = scan_pci_dir(&dev, amd_accuracy, ignore_disabled, info_flags) else {
+            let Ok(Some(mut gpu)) = scan_pci_dir(&dev, ids, amd_ids, ignore_disabled, info_flags) else {
                 continue;
             };
             if let Some(start) = gpu.model.find('[') {
@@ -187,7 +256,7 @@ fn fill_from_drivers(gpus: &mut Vec<GPUInfo>, amd_accuracy: bool, ignore_disable
     Ok(())
 }
 
-fn fill_from_pcisysfile(gpus: &mut Vec<GPUInfo>, amd_accuracy: bool, ignore_disabled: bool, info_flags: u32) -> Result<(), ModuleError> {
+fn fill_from_pcisysfile(gpus: &mut Vec<GPUInfo>, ids: Option<&IdsIndex>, amd_ids: Option<&IdsIndex>, ignore_disabled: bool, info_flags: u32) -> Result<(), ModuleError> {
     let dir: ReadDir = match fs::read_dir("/sys/bus/pci/devices") {
         Ok(r) => r,
         Err(e) => return Err(ModuleError::new("GPU", format!("Can't read from /sys/bus/pci/devices: {e}"))),
@@ -201,7 +270,7 @@ fn fill_from_pcisysfile(gpus: &mut Vec<GPUInfo>, amd_accuracy: bool, ignore_disa
             continue;
         };
 
-        let Ok(Some(mut gpu)) = scan_pci_dir(&d, amd_accuracy, ignore_disabled, info_flags) else {
+        let Ok(Some(mut gpu)) = scan_pci_dir(&d, ids, amd_ids, ignore_disabled, info_flags) else {
             continue;
         };
         if let Some(start) = gpu.model.find('[') {
@@ -217,29 +286,16 @@ fn fill_from_pcisysfile(gpus: &mut Vec<GPUInfo>, amd_accuracy: bool, ignore_disa
 }
 
 
-fn scan_pci_dir(d: &DirEntry, amd_accuracy: bool, ignore_disabled: bool, info_flags: u32) -> Result<Option<GPUInfo>, ModuleError> {
-    // Credit: https://wiki.archlinux.org/title/Modalias
-    // v - Vendor ID
-    // d - Device ID
-    // sv/sd - Subsys Vendor/Device, not needed for us (although could posssibly identify gpu vendor info?) 
-    // bc - Base Class 
-    // sc - Sub-class, not needed for us
-    // i - Programming interface, again useless for us
-    // From what I can see, these are all in a fixed length so we can just use substringing to
-    // find our info opposed to any fancy parsing shit
-    // An example from my GPU: v00001002d0000747Esv00001DA2sd0000D475bc03sc00i00
-    let Ok(modalias) = util::file_read(&d.path().join("modalias")) else {
+fn scan_pci_dir(d: &DirEntry, ids: Option<&IdsIndex>, amd_ids: Option<&IdsIndex>, ignore_disabled: bool, info_flags: u32) -> Result<Option<GPUInfo>, ModuleError> {
+    let Some(device) = pci::read_device(&d.path()) else {
         return Ok(None);
     };
-    let modalias_contents: String = modalias[4..].to_string(); // strip the prefixing "pci:"
 
     // Check the class first things first, checking it's a display device 
     // Only checking the base class for it being 0x03 
-    // v00001002d0000747Esv00001DA2sd0000D475bc03sc00i00
-    let base_class: &str = &modalias_contents[40..42];
     // Not a display device
     // And yes, I'm doing this check with a string instead of parsing it with an AND, fuck you.
-    if base_class != "03" {
+    if device.class != "03" {
         return Ok(None);
     }
 
@@ -255,150 +311,232 @@ fn scan_pci_dir(d: &DirEntry, amd_accuracy: bool, ignore_disabled: bool, info_fl
 
     // Device info time 
     let mut gpu: GPUInfo = GPUInfo::new();
+    gpu.vendor_id.clone_from(&device.vendor_id);
+    gpu.device_id.clone_from(&device.device_id);
     // Vendor/Device
     if is_flag_set_u32(info_flags, GPU_INFOFLAG_MODEL) || is_flag_set_u32(info_flags, GPU_INFOFLAG_VENDOR) {
-        let vendor_id: String = modalias_contents[5..9].to_string();
-        let device_id: String = modalias_contents[14..18].to_string();
-
         // Being more accurate with AMD
-        if vendor_id == "1002" && amd_accuracy {
+        if let (Some(amd_ids), "1002") = (amd_ids, device.vendor_id.as_str()) {
             gpu.vendor = String::from("Advanced Micro Devices, Inc. [AMD/ATI]");
             let revision_id: String = match util::file_read(&d.path().join("revision")) {
                 Ok(r) => r[2..].trim().to_string(),
                 Err(e) => return Err(ModuleError::new("GPU", format!("Can't read from file: {e}"))),
             };
-            if let Some(r) = search_amd_model(&device_id, &revision_id)? {
+            if let Some(r) = search_amd_model(amd_ids, &device.device_id, &revision_id) {
                 gpu.model = r;
             }
         }
         if gpu.model == "Unknown" {
-            (gpu.vendor, gpu.model) = search_pci_ids(&vendor_id, &device_id)?;
+            // Missing pci.ids or an outdated one shouldn't take down the whole module
+            (gpu.vendor, gpu.model) = search_pci_ids(ids, &device.vendor_id, &device.device_id)
+                .unwrap_or((String::new(), device.device_id.clone()));
+            if gpu.vendor.is_empty() || gpu.model == device.device_id {
+                let driver: Option<String> = fs::read_link(d.path().join("driver")).ok()
+                    .and_then(|x| Some(x.file_name()?.to_str()?.to_string()));
+                let (vendor, model) = hwids::fallback_pci_names(&device.vendor_id, &device.device_id, driver.as_deref());
+                if gpu.vendor.is_empty() {
+                    gpu.vendor = vendor;
+                }
+                if gpu.model == device.device_id {
+                    gpu.model = model;
+                }
+            }
         }
     }
 
-    // Finally, Vram
+    // Finally, everything else the driver tells us
+    fill_from_sysfs(&d.path(), &mut gpu, info_flags)?;
+
+    Ok(Some(gpu))
+}
+
+// Reads everything we can from the device's directory in sysfs
+// Kept separate from the PCI scanning so that it can be tested against a fake sysfs tree
+fn fill_from_sysfs(path: &Path, gpu: &mut GPUInfo, info_flags: u32) -> Result<(), ModuleError> {
+    if let Some(slot) = path.file_name().and_then(|x| x.to_str()) {
+        gpu.slot = slot.to_string();
+    }
+    // The driver is a symlink to it's directory in /sys/bus/pci/drivers
+    let driver: Option<String> = fs::read_link(path.join("driver")).ok()
+        .and_then(|x| Some(x.file_name()?.to_str()?.to_string()));
+    if is_flag_set_u32(info_flags, GPU_INFOFLAG_DRIVER) {
+        gpu.driver = driver.clone().unwrap_or("None".to_string());
+    }
+
+    // amdgpu only
     if is_flag_set_u32(info_flags, GPU_INFOFLAG_VRAM) {
-        if let Ok(r) = util::file_read(&d.path().join("mem_info_vram_total")) {
+        if let Ok(r) = util::file_read(&path.join("mem_info_vram_total")) {
             gpu.vram_mb = match u32::try_from(r.trim().parse::<u64>().unwrap() / 1024 / 1024) {
                 Ok(r) => r,
                 Err(e) => return Err(ModuleError::new("GPU", format!("Failed to convert vram to u32: {e}"))),
             }
         }
     }
+    if is_flag_set_u32(info_flags, GPU_INFOFLAG_VRAM_USED) {
+        if let Ok(r) = util::file_read(&path.join("mem_info_vram_used")) {
+            gpu.vram_used_mb = r.trim().parse::<u64>().ok().and_then(|x| u32::try_from(x / 1024 / 1024).ok());
+        }
+    }
+    if is_flag_set_u32(info_flags, GPU_INFOFLAG_BUSY) {
+        if let Ok(r) = util::file_read(&path.join("gpu_busy_percent")) {
+            gpu.busy_percent = r.trim().parse::<u32>().ok();
+        }
+    }
 
-    Ok(Some(gpu))
-}
-
-// This uses pci.ids inside pciutils to identify devices:
-// https://man.archlinux.org/man/core/pciutils/pci.ids.5.en
-//
-// To prevent having to relicence to GPL I don't distribute a copy of this, and simply have to
-// rely on the host's copy. Problem then becomes that different distros seem to place this file
-// in different places
-// I'll try to find it in as many places as possible but ultimately can't cover every place. If
-// you know the places, make a PR/Issue and i'll add it in. Fucking hate licences that work
-// like this but oh well.
-fn search_pci_ids(vendor: &str, device: &str) -> Result<(String, String), ModuleError> {
-    // Search all known locations
-    let ids_path: &Path = match util::find_first_path_exists(vec![
-        Path::new("/usr/share/hwdata/pci.ids"),
-        Path::new("/usr/share/misc/pci.ids")
-    ]) {
-        Some(r) => r,
-        None => return Err(ModuleError::new("GPU", "Could not find an appropriate path for getting PCI ID info.".to_string()))
-    };
+    if is_flag_set_u32(info_flags, GPU_INFOFLAG_CLOCKS) {
+        (gpu.clock_mhz, gpu.max_clock_mhz) = match driver.as_deref() {
+            Some("amdgpu" | "radeon") => read_amd_clocks(path),
+            Some("i915") => read_i915_clocks(path),
+            Some("xe") => read_xe_clocks(path),
+            // nouveau only has these in debugfs, which needs root, and NVIDIA's own driver
+            // doesn't expose them at all
+            _ => (None, None)
+        };
+    }
 
-    let file: File = match File::open(ids_path) {
-        Ok(r) => r,
-        Err(e) => return Err(ModuleError::new("GPU", format!("Can't read from {} - {e}", ids_path.display()))),
-    };
-    let buffer: BufReader<File> = BufReader::new(file);
-
-    // parsing this file is weird
-    let mut vendor_result: String = String::new();
-    let mut device_result: String = String::new();
-    // Find the vendor ID + device in the list
-    let vendor_term: String = String::from(vendor).to_lowercase();
-    let dev_term: String = (String::from('\t') + device).to_lowercase();
-    let mut in_vendor: bool = false;
-    for line in buffer.lines() { 
-        if line.is_err() {
-            continue;
-        }
-        let line: String = line.unwrap();
+    if is_flag_set_u32(info_flags, GPU_INFOFLAG_TYPE) {
+        gpu.gpu_type = if is_integrated(path) {
+            "Integrated".to_string()
+        } else {
+            "Discrete".to_string()
+        };
+    }
 
-        if line.trim().starts_with('#') {
-            continue
-        }
+    Ok(())
+}
 
-        if in_vendor && line.chars().next().is_some() {
-            in_vendor = line.chars().next().unwrap().is_whitespace();
-            if !in_vendor {
-                // Assume we missed it
-                break
+// There's no flag for this, so it's a best guess
+fn is_integrated(path: &Path) -> bool {
+    // Discrete cards map at least 256MB of their VRAM through one of their BARs, or all of it with
+    // resizable BAR, whereas integrated ones only get a small carve out of system memory
+    // Each line in resource is the start, end and flags of a BAR
+    let largest_bar: u64 = util::file_read(&path.join("resource")).unwrap_or_default().lines()
+        .filter_map(|x| {
+            let values: Vec<u64> = x.split_whitespace()
+                .filter_map(|x| u64::from_str_radix(x.trim_start_matches("0x"), 16).ok())
+                .collect();
+            if values.len() < 2 || values[1] <= values[0] {
+                return None;
             }
-        }
+            Some(values[1] - values[0] + 1)
+        })
+        .max()
+        .unwrap_or(0);
+    if largest_bar > 256 * 1024 * 1024 {
+        return false;
+    }
+    // Exactly 256MB could be either an APU or a discrete card without resizable BAR
+    if largest_bar != 0 && largest_bar < 256 * 1024 * 1024 {
+        return true;
+    }
 
-        if line.starts_with(&vendor_term) && vendor_result.is_empty() {
-            // Assume the first hit of this is our full vendor name
-            vendor_result = line[vendor_term.len()..].trim().to_string();
-            in_vendor = true;
-        } else if line.starts_with(&dev_term) && in_vendor {
-            // And here's the device name
-            device_result = line[dev_term.len()..].trim().to_string();
-            break
-        }
+    // Integrated GPUs are part of the CPU, so they sit directly on the root bus (0000:00:02.0)
+    // while discrete ones hang off of a PCIe port
+    let slot: &str = path.file_name().and_then(|x| x.to_str()).unwrap_or_default();
+    if is_root_bus(slot) {
+        return true;
     }
 
-    if device_result.is_empty() {
-        device_result += device;
+    // AMD APUs don't always sit on the root bus, but with switchable graphics the firmware boots
+    // off of the integrated GPU, leaving the discrete one behind a PCIe port for later
+    // boot_vga only exists for display controllers, and is 1 on the one the firmware used, so
+    // being the boot GPU only counts when there's another non root bus GPU to switch to
+    if util::file_read(&path.join("boot_vga")).is_ok_and(|x| x.trim() == "1") {
+        let Ok(dir) = fs::read_dir("/sys/bus/pci/devices") else {
+            return false;
+        };
+        return dir.flatten().any(|x| {
+            let other: String = x.file_name().to_string_lossy().to_string();
+            other != slot && !is_root_bus(&other) && x.path().join("boot_vga").exists()
+        });
     }
 
-    Ok((vendor_result.to_string(), device_result.to_string()))
+    false
+}
+fn is_root_bus(slot: &str) -> bool {
+    slot.split(':').nth(1) == Some("00")
 }
-fn search_amd_model(device: &str, revision: &str) -> Result<Option<String>, ModuleError> {
-    let ids_path: &Path = match util::find_first_path_exists(vec![
-        Path::new("/usr/share/libdrm/amdgpu.ids")
-    ]) {
-        Some(r) => r,
-        None => return Err(ModuleError::new("GPU", "Could not find an appropriate path for getting AMD PCI ID info.".to_string()))
-    };
 
-    let file: File = match File::open(ids_path) {
-        Ok(r) => r,
-        Err(e) => return Err(ModuleError::new("GPU", format!("Can't read from {} - {e}", ids_path.display()))),
+// Gives back the current and max core clock
+// pp_dpm_sclk lists each clock level, with the current one marked with a *, e.g "1: 2100Mhz *"
+fn read_amd_clocks(path: &Path) -> (Option<u32>, Option<u32>) {
+    let Ok(contents) = util::file_read(&path.join("pp_dpm_sclk")) else {
+        return (None, None);
     };
-    let buffer: BufReader<File> = BufReader::new(file);
 
-    let mut device_result: String = String::new();
-    let dev_term: String = format!("{device},\t{revision},\t").to_lowercase().to_string();
-    for line in buffer.lines() { 
-        if line.is_err() {
+    let mut current: Option<u32> = None;
+    let mut max: Option<u32> = None;
+    for line in contents.lines() {
+        let Some((_, value)) = line.split_once(':') else {
+            continue;
+        };
+        let Some(clock) = value.trim().trim_end_matches('*').trim().to_lowercase().strip_suffix("mhz").and_then(|x| x.parse::<u32>().ok()) else {
             continue;
+        };
+        if line.trim_end().ends_with('*') {
+            current = Some(clock);
         }
-        let line: String = line.unwrap();
+        max = max.max(Some(clock));
+    }
 
-        if line.trim().starts_with('#') {
-            continue
+    (current, max)
+}
+fn read_i915_clocks(path: &Path) -> (Option<u32>, Option<u32>) {
+    // These live with the DRM card rather than the PCI device, e.g device/drm/card0
+    let Ok(dir) = fs::read_dir(path.join("drm")) else {
+        return (None, None);
+    };
+    for entry in dir.flatten() {
+        let card_path: PathBuf = entry.path();
+        if !entry.file_name().to_string_lossy().starts_with("card") {
+            continue;
         }
 
-        if line.to_lowercase().starts_with(&dev_term) {
-            device_result = line[dev_term.len()..].to_string();
-            break
-        }
+        let current: Option<u32> = read_mhz(&card_path.join("gt_cur_freq_mhz"));
+        // RP0 is the hardware's max, gt_max_freq_mhz is the user adjustable max
+        let max: Option<u32> = read_mhz(&card_path.join("gt_RP0_freq_mhz")).or_else(|| read_mhz(&card_path.join("gt_max_freq_mhz")));
+        return (current, max);
     }
 
-    if device_result.is_empty() {
-        return Ok(None)
-    }
+    (None, None)
+}
+fn read_xe_clocks(path: &Path) -> (Option<u32>, Option<u32>) {
+    // https://docs.kernel.org/gpu/xe/xe_gt_freq.html
+    let freq_path: PathBuf = path.join("tile0/gt0/freq0");
+    (read_mhz(&freq_path.join("act_freq")).or_else(|| read_mhz(&freq_path.join("cur_freq"))), read_mhz(&freq_path.join("rp0_freq")).or_else(|| read_mhz(&freq_path.join("max_freq"))))
+}
+fn read_mhz(path: &Path) -> Option<u32> {
+    util::file_read(path).ok()?.trim().parse::<u32>().ok()
+}
+
+// Names come from pci.ids, see common_sources/hwids.rs
+fn search_pci_ids(ids: Option<&IdsIndex>, vendor: &str, device: &str) -> Result<(String, String), ModuleError> {
+    let Some(ids) = ids else {
+        return Err(ModuleError::new("GPU", "Could not find an appropriate path for getting PCI ID info.".to_string()));
+    };
+    Ok(hwids::search_ids(ids, vendor, device))
+}
+fn open_amd_ids() -> Result<IdsIndex, ModuleError> {
+    let ids_path: &Path = match util::find_first_path_exists(vec![
+        Path::new("/usr/share/libdrm/amdgpu.ids")
+    ]) {
+        Some(r) => r,
+        None => return Err(ModuleError::new("GPU", "Could not find an appropriate path for getting AMD PCI ID info.".to_string()))
+    };
 
-    Ok(Some(device_result.to_string()))
+    match IdsIndex::open(ids_path, IdsFormat::Amdgpu) {
+        Ok(r) => Ok(r),
+        Err(e) => Err(ModuleError::new("GPU", e)),
+    }
+}
+fn search_amd_model(amd_ids: &IdsIndex, device: &str, revision: &str) -> Option<String> {
+    amd_ids.amdgpu_model(device, revision)
 }
 
 mod tests {
     #[test]
     fn test_pci_ids_normal() {
-        use crate::{module::ModuleError, modules::gpu::search_pci_ids};
+        use crate::{common_sources::{hwids, ids_index::IdsIndex}, module::ModuleError, modules::gpu::search_pci_ids};
         use std::collections::HashMap;
 
         let tests: HashMap<(&str, &str), (&str, &str)> = HashMap::from([
@@ -418,8 +556,9 @@ mod tests {
             // (("8086", "e20b"), ("Intel Corporation", "Battlemage G21 [Arc B580]"))
         ]);
 
+        let ids: Option<IdsIndex> = hwids::find_pci_ids(None).and_then(|x| hwids::open_ids(&x).ok());
         for test in tests {
-            let result: Result<(String, String), ModuleError> = search_pci_ids(test.0.0, test.0.1);
+            let result: Result<(String, String), ModuleError> = search_pci_ids(ids.as_ref(), test.0.0, test.0.1);
             assert!(result.is_ok());
             let result: (String, String) = result.unwrap();
             assert_eq!(result.0, test.1.0);
@@ -430,7 +569,7 @@ mod tests {
     // AMD Accuracy
     #[test]
     fn test_pci_ids_amd() {
-        use crate::{module::ModuleError, modules::gpu::search_amd_model};
+        use crate::{common_sources::ids_index::IdsIndex, module::ModuleError, modules::gpu::{open_amd_ids, search_amd_model}};
         use std::collections::HashMap;
 
         let tests: HashMap<(&str, &str), &str> = HashMap::from([
@@ -439,13 +578,108 @@ mod tests {
             (("744c", "c8"), "AMD Radeon RX 7900 XTX"),
         ]);
 
+        let amd_ids: Result<IdsIndex, ModuleError> = open_amd_ids();
+        assert!(amd_ids.is_ok());
+        let amd_ids: IdsIndex = amd_ids.unwrap();
         for test in tests {
-            let result: Result<Option<String>, ModuleError> = search_amd_model(test.0.0, test.0.1);
-            assert!(result.is_ok());
-            let result: Option<String> = result.unwrap();
+            let result: Option<String> = search_amd_model(&amd_ids, test.0.0, test.0.1);
             assert!(result.is_some());
             let result: String = result.unwrap();
             assert_eq!(result, test.1);
         }
     }
+
+    // Builds a fake PCI device directory for the sysfs tests, under the temp dir
+    // Tests run in parallel, so each call gets it's own root to avoid them clobbering each other
+    #[allow(dead_code)]
+    fn make_fixture(slot: &str, driver: &str, files: &[(&str, &str)]) -> std::path::PathBuf {
+        static FIXTURE_COUNT: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+        let id: u32 = FIXTURE_COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let root: std::path::PathBuf = std::env::temp_dir().join(format!("crabfetch-gpu-{}-{id}", std::process::id()));
+        let path: std::path::PathBuf = root.join(slot);
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("drivers").join(driver)).unwrap();
+        std::fs::create_dir_all(&path).unwrap();
+        std::os::unix::fs::symlink(root.join("drivers").join(driver), path.join("driver")).unwrap();
+        for (file, contents) in files {
+            let file_path: std::path::PathBuf = path.join(file);
+            std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+            std::fs::write(file_path, contents).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_gpu_sysfs_amd() {
+        use crate::{module::Module, modules::gpu::{fill_from_sysfs, GPUInfo}};
+
+        let path: std::path::PathBuf = make_fixture("0000:03:00.0", "amdgpu", &[
+            ("mem_info_vram_total", "17163091968\n"),
+            ("mem_info_vram_used", "1073741824\n"),
+            ("gpu_busy_percent", "37\n"),
+            ("pp_dpm_sclk", "0: 500Mhz \n1: 1274Mhz *\n2: 2565Mhz \n"),
+        ]);
+        let mut gpu: GPUInfo = GPUInfo::new();
+        let info_flags: u32 = GPUInfo::gen_info_flags("{vram} {vram_used} {driver} {type} {clock} {busy}");
+        assert!(fill_from_sysfs(&path, &mut gpu, info_flags).is_ok());
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(gpu.slot, "0000:03:00.0");
+        assert_eq!(gpu.driver, "amdgpu");
+        assert_eq!(gpu.vram_mb, 16368);
+        assert_eq!(gpu.vram_used_mb, Some(1024));
+        assert_eq!(gpu.busy_percent, Some(37));
+        assert_eq!(gpu.clock_mhz, Some(1274));
+        assert_eq!(gpu.max_clock_mhz, Some(2565));
+        assert_eq!(gpu.gpu_type, "Discrete");
+
+        // An APU off of the root bus, only mapping a small BAR
+        let path: std::path::PathBuf = make_fixture("0000:05:00.0", "amdgpu", &[
+            ("mem_info_vram_total", "536870912\n"),
+            ("resource", "0x0000000080000000 0x0000000083ffffff 0x000000000014220c\n0x0000000000000000 0x0000000000000000 0x0000000000000000\n"),
+        ]);
+        let mut gpu: GPUInfo = GPUInfo::new();
+        assert!(fill_from_sysfs(&path, &mut gpu, GPUInfo::gen_info_flags("{type}")).is_ok());
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(gpu.vram_mb, 0);
+        assert_eq!(gpu.gpu_type, "Integrated");
+    }
+
+    #[test]
+    fn test_gpu_sysfs_intel() {
+        use crate::{module::Module, modules::gpu::{fill_from_sysfs, GPUInfo}};
+
+        // i915, sitting on the root bus
+        let path: std::path::PathBuf = make_fixture("0000:00:02.0", "i915", &[
+            ("drm/card1/gt_cur_freq_mhz", "350\n"),
+            ("drm/card1/gt_RP0_freq_mhz", "1300\n"),
+            ("drm/card1/gt_max_freq_mhz", "1100\n"),
+        ]);
+        let mut gpu: GPUInfo = GPUInfo::new();
+        let info_flags: u32 = GPUInfo::gen_info_flags("{driver} {type} {clock}");
+        assert!(fill_from_sysfs(&path, &mut gpu, info_flags).is_ok());
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(gpu.driver, "i915");
+        assert_eq!(gpu.clock_mhz, Some(350));
+        assert_eq!(gpu.max_clock_mhz, Some(1300));
+        assert_eq!(gpu.busy_percent, None);
+        assert_eq!(gpu.gpu_type, "Integrated");
+
+        // xe, as a discrete card with a large BAR
+        let path: std::path::PathBuf = make_fixture("0000:03:00.0", "xe", &[
+            ("tile0/gt0/freq0/act_freq", "2000\n"),
+            ("tile0/gt0/freq0/rp0_freq", "2450\n"),
+            ("resource", "0x00000000a0000000 0x00000000a0ffffff 0x0000000000040200\n0x0000004000000000 0x00000041ffffffff 0x000000000014220c\n0x0000000000000000 0x0000000000000000 0x0000000000000000\n"),
+        ]);
+        let mut gpu: GPUInfo = GPUInfo::new();
+        assert!(fill_from_sysfs(&path, &mut gpu, info_flags).is_ok());
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(gpu.driver, "xe");
+        assert_eq!(gpu.clock_mhz, Some(2000));
+        assert_eq!(gpu.max_clock_mhz, Some(2450));
+        assert_eq!(gpu.gpu_type, "Discrete");
+    }
 }
@@ -5,11 +5,13 @@ use std::path::Path;
 use {android_system_properties::AndroidSystemProperties, std::env};
 use serde::Deserialize;
 
-use crate::{config_manager::Configuration, formatter::CrabFetchColor, module::Module, util::{self, is_flag_set_u32}, ModuleError};
+use crate::{common_sources::virtualization, config_manager::Configuration, formatter::CrabFetchColor, module::Module, util::{self, is_flag_set_u32}, ModuleError};
 
 pub struct HostInfo {
     host: String,
-    chassis: String
+    chassis: String,
+    hypervisor: Option<String>,
+    container: Option<String>
 }
 #[derive(Deserialize)]
 pub struct HostConfiguration {
@@ -27,7 +29,9 @@ impl Module for HostInfo {
     fn new() -> HostInfo {
         HostInfo {
             host: "Unknown".to_string(),
-            chassis: "Unknown".to_string()
+            chassis: "Unknown".to_string(),
+            hypervisor: None,
+            container: None
         }
     }
 
@@ -50,7 +54,9 @@ impl Module for HostInfo {
 
         let title: String = config.host.title
             .replace("{host}", "Unknown")
-            .replace("{chassis}", "Unknown");
+            .replace("{chassis}", "Unknown")
+            .replace("{hypervisor}", "Unknown")
+            .replace("{container}", "Unknown");
 
         Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
     }
@@ -58,6 +64,8 @@ impl Module for HostInfo {
     fn replace_placeholders(&self, text: &str, _: &Configuration) -> String {
         text.replace("{host}", &self.host)
             .replace("{chassis}", &self.chassis)
+            .replace("{hypervisor}", self.hypervisor.as_deref().unwrap_or("None"))
+            .replace("{container}", self.container.as_deref().unwrap_or("None"))
     }
 
     fn gen_info_flags(format: &str) -> u32 {
@@ -70,6 +78,12 @@ impl Module for HostInfo {
         if format.contains("{chassis}") {
             info_flags |= HOST_INFOFLAG_CHASSIS;
         }
+        if format.contains("{hypervisor}") {
+            info_flags |= HOST_INFOFLAG_HYPERVISOR;
+        }
+        if format.contains("{container}") {
+            info_flags |= HOST_INFOFLAG_CONTAINER;
+        }
 
         info_flags
     }
@@ -90,6 +104,8 @@ impl HostInfo {
 
 const HOST_INFOFLAG_HOST: u32 = 1;
 const HOST_INFOFLAG_CHASSIS: u32 = 2;
+const HOST_INFOFLAG_HYPERVISOR: u32 = 4;
+const HOST_INFOFLAG_CONTAINER: u32 = 8;
 
 pub fn get_host(config: &Configuration) -> Result<HostInfo, ModuleError> {
     let mut host: HostInfo = HostInfo::new();
@@ -100,6 +116,14 @@ pub fn get_host(config: &Configuration) -> Result<HostInfo, ModuleError> {
     }
     let info_flags: u32 = HostInfo::gen_info_flags(&format);
 
+    // Done first so that it's still filled in on WSL
+    if is_flag_set_u32(info_flags, HOST_INFOFLAG_HYPERVISOR) {
+        host.hypervisor = virtualization::detect_hypervisor();
+    }
+    if is_flag_set_u32(info_flags, HOST_INFOFLAG_CONTAINER) {
+        host.container = virtualization::detect_container();
+    }
+
     // Android 
     #[cfg(feature = "android")]
     if env::consts::OS == "android" {
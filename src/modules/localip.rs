@@ -1,12 +1,26 @@
-use std::{fs::{self, ReadDir}, mem, net::{IpAddr, Ipv4Addr, Ipv6Addr}};
+use std::{fs::{self, ReadDir}, net::{IpAddr, Ipv4Addr, Ipv6Addr}};
 
 use serde::Deserialize;
 
-use crate::{config_manager::Configuration, formatter::CrabFetchColor, module::Module, util, ModuleError};
+use crate::{common_sources::syscalls::{InterfaceAddress, SyscallCache}, config_manager::Configuration, formatter::CrabFetchColor, module::Module, ModuleError};
 
 pub struct LocalIPInfo {
     interface: String,
-    ip_addr: String,
+    addresses: Vec<LocalIPAddress>  // Only ever 1 unless we're collapsing them
+}
+pub struct LocalIPAddress {
+    addr: IpAddr,
+    netmask: Option<IpAddr>,
+    prefix_length: Option<u32>,
+    broadcast: Option<IpAddr>,
+    scope: &'static str
+}
+#[derive(Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalIPAddressFamily {
+    Both,
+    Ipv4,
+    Ipv6
 }
 #[derive(Deserialize)]
 pub struct LocalIPConfiguration {
@@ -15,13 +29,17 @@ pub struct LocalIPConfiguration {
     pub title_bold: Option<bool>,
     pub title_italic: Option<bool>,
     pub separator: Option<String>,
-    pub format: String
+    pub format: String,
+    pub address_family: LocalIPAddressFamily,
+    pub include_virtual: Vec<String>,
+    pub exclude_scopes: Vec<String>,
+    pub collapse: bool
 }
 impl Module for LocalIPInfo {
     fn new() -> LocalIPInfo {
         LocalIPInfo {
             interface: "Unknown".to_string(),
-            ip_addr: "Unknown".to_string()
+            addresses: Vec::new()
         }
     }
 
@@ -44,14 +62,32 @@ impl Module for LocalIPInfo {
 
         let title: String = config.locale.title
             .replace("{interface}", "Unknown")
-            .replace("{addr}", "Unknown");
+            .replace("{addr}", "Unknown")
+            .replace("{cidr}", "Unknown")
+            .replace("{prefix}", "Unknown")
+            .replace("{netmask}", "Unknown")
+            .replace("{broadcast}", "Unknown")
+            .replace("{scope}", "Unknown");
 
         Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
     }
 
     fn replace_placeholders(&self, text: &str, _: &Configuration) -> String {
+        // When collapsed, each placeholder lists the values for every address
+        let join = |value: fn(&LocalIPAddress) -> String| -> String {
+            self.addresses.iter().map(value).collect::<Vec<String>>().join(", ")
+        };
+
         text.replace("{interface}", &self.interface)
-            .replace("{addr}", &self.ip_addr)
+            .replace("{addr}", &join(|x| x.addr.to_string()))
+            .replace("{cidr}", &join(|x| match x.prefix_length {
+                Some(r) => format!("{}/{r}", x.addr),
+                None => x.addr.to_string()
+            }))
+            .replace("{prefix}", &join(|x| x.prefix_length.map_or("Unknown".to_string(), |r| r.to_string())))
+            .replace("{netmask}", &join(|x| x.netmask.map_or("Unknown".to_string(), |r| r.to_string())))
+            .replace("{broadcast}", &join(|x| x.broadcast.map_or("None".to_string(), |r| r.to_string())))
+            .replace("{scope}", &join(|x| x.scope.to_string()))
     }
 
     fn gen_info_flags(_: &str) -> u32 {
@@ -59,8 +95,7 @@ impl Module for LocalIPInfo {
     }
 }
 
-#[allow(clippy::cast_ptr_alignment)] // i'm really shitty with dealing with pointers, but afaik its fine 
-pub fn get_local_ips() -> Result<Vec<LocalIPInfo>, ModuleError> {
+pub fn get_local_ips(config: &Configuration, syscall_cache: &mut SyscallCache) -> Result<Vec<LocalIPInfo>, ModuleError> {
     // no info flags here as it's all from the same source
     let mut addrs: Vec<LocalIPInfo> = Vec::new();
 
@@ -80,72 +115,84 @@ pub fn get_local_ips() -> Result<Vec<LocalIPInfo>, ModuleError> {
         virt_interfaces.push(d.file_name().into_string().unwrap());
     }
 
-    // Credit to this guy's example https://users.rust-lang.org/t/best-way-to-get-your-own-ips/14308/2
-    // (Adapted a bit) 
-    unsafe {
-        // Get the first one
-        let mut ifaddr: *mut libc::ifaddrs = mem::zeroed();
-        if libc::getifaddrs(&mut ifaddr) != 0 {
-            return Err(ModuleError::new("LocalIP", "getifaddrs syscall failed!".to_string()));
+    let interface_addrs: Vec<InterfaceAddress> = match syscall_cache.get_ifaddrs_cached() {
+        Ok(r) => r,
+        Err(e) => return Err(ModuleError::new("LocalIP", e)),
+    };
+    for interface_addr in interface_addrs {
+        // Ignore any virtual devices, unless the user specifically wants them
+        if virt_interfaces.contains(&interface_addr.interface) && !config.localip.include_virtual.contains(&interface_addr.interface) {
+            continue;
         }
 
-        let mut ifaddrs: libc::ifaddrs = *ifaddr;
-        let mut inf_loop_protection: u8 = 0;
-        // Go through every interface
-        loop {
-            inf_loop_protection += 1;
-            let interface_name: String = match util::cstr_from_ptr(ifaddrs.ifa_name) {
-                Ok(r) => r,
-                Err(e) => return Err(ModuleError::new("LocalIP", format!("Failed to convert interface name into Rust string: {e}")))
-            };
+        match config.localip.address_family {
+            LocalIPAddressFamily::Ipv4 if !interface_addr.addr.is_ipv4() => continue,
+            LocalIPAddressFamily::Ipv6 if !interface_addr.addr.is_ipv6() => continue,
+            _ => {}
+        }
 
-            // Ignore any virtual devices
-            if virt_interfaces.contains(&interface_name) {
-                if ifaddrs.ifa_next.is_null() || inf_loop_protection > 25 {
-                    break;
-                }
-                ifaddrs = *ifaddrs.ifa_next;
-                continue;
-            }
+        let scope: &'static str = match interface_addr.addr {
+            IpAddr::V4(r) => classify_ipv4(r),
+            IpAddr::V6(r) => classify_ipv6(r),
+        };
+        if config.localip.exclude_scopes.iter().any(|x| x == scope) {
+            continue;
+        }
 
-            // the spooky part, at least for me
-            if i32::from((*ifaddrs.ifa_addr).sa_family) == libc::AF_INET {
-                // ipv4
-                let addr: *mut libc::sockaddr_in = (ifaddrs.ifa_addr).cast::<libc::sockaddr_in>();
-                let ipaddr: Ipv4Addr = Ipv4Addr::from((*addr).sin_addr.s_addr.to_be());
-
-                let data: LocalIPInfo = LocalIPInfo {
-                    interface: interface_name,
-                    ip_addr: IpAddr::V4(ipaddr).to_string()
-                };
-                addrs.push(data);
-            } else if i32::from((*ifaddrs.ifa_addr).sa_family) == libc::AF_INET6 {
-                // ipv6
-                let addr: *mut libc::sockaddr_in6 = (ifaddrs.ifa_addr).cast::<libc::sockaddr_in6>();
-                // https://man7.org/linux/man-pages/man7/ipv6.7.html
-                // "Linux supports it only for link-local addresses"
-                // I'm guessing that means if it's not 0, it's not a global IP, and thus not a
-                // "real" ip and should be discarded? Not too sure tbh
-                if (*addr).sin6_scope_id == 0 {
-                    let ipaddr: Ipv6Addr = Ipv6Addr::from((*addr).sin6_addr.s6_addr);
-
-                    let mut interface_name: String = interface_name.clone();
-                    interface_name.push_str(" (v6)");
-
-                    let data: LocalIPInfo = LocalIPInfo {
-                        interface: interface_name,
-                        ip_addr: IpAddr::V6(ipaddr).to_string()
-                    };
-                    addrs.push(data);
-                }
-            }
+        let address: LocalIPAddress = LocalIPAddress {
+            addr: interface_addr.addr,
+            netmask: interface_addr.netmask,
+            prefix_length: interface_addr.prefix_length(),
+            broadcast: interface_addr.broadcast,
+            scope
+        };
 
-            if ifaddrs.ifa_next.is_null() || inf_loop_protection > 25 {
-                break;
+        if config.localip.collapse {
+            if let Some(existing) = addrs.iter_mut().find(|x| x.interface == interface_addr.interface) {
+                existing.addresses.push(address);
+                continue;
             }
-            ifaddrs = *ifaddrs.ifa_next;
+            addrs.push(LocalIPInfo {
+                interface: interface_addr.interface,
+                addresses: vec![address]
+            });
+        } else {
+            let interface: String = if address.addr.is_ipv6() {
+                format!("{} (v6)", interface_addr.interface)
+            } else {
+                interface_addr.interface
+            };
+            addrs.push(LocalIPInfo {
+                interface,
+                addresses: vec![address]
+            });
         }
     }
 
     Ok(addrs)
 }
+
+fn classify_ipv4(addr: Ipv4Addr) -> &'static str {
+    if addr.is_loopback() {
+        "loopback"
+    } else if addr.is_link_local() {
+        "link-local"
+    } else if addr.is_private() {
+        "private"
+    } else {
+        "global"
+    }
+}
+fn classify_ipv6(addr: Ipv6Addr) -> &'static str {
+    // https://www.iana.org/assignments/ipv6-address-space/ipv6-address-space.xhtml
+    let first_segment: u16 = addr.segments()[0];
+    if addr.is_loopback() {
+        "loopback"
+    } else if first_segment & 0xFFC0 == 0xFE80 {
+        "link-local"
+    } else if first_segment & 0xFE00 == 0xFC00 {
+        "ula"
+    } else {
+        "global"
+    }
+}
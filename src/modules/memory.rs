@@ -3,12 +3,43 @@ use std::io::{BufRead, BufReader};
 
 use serde::Deserialize;
 
-use crate::{formatter::{self, CrabFetchColor}, config_manager::Configuration, module::Module, ModuleError};
+use crate::{common_sources::{cgroups, smbios::{self, SMBIOSStructure}}, config_manager::Configuration, formatter::{self, CrabFetchColor}, module::Module, util::is_flag_set_u32, ModuleError};
 
 pub struct MemoryInfo {
     used_kb: u64,
     max_kb: u64,
-    percentage: f32
+    percentage: f32,
+    free_kb: u64,
+    available_kb: u64,
+    cached_kb: u64,
+    buffers_kb: u64,
+    shared_kb: u64,
+    dirty_kb: u64,
+    hugepages_kb: u64,
+    slab_kb: u64,
+    slab_reclaimable_kb: u64,
+    committed_kb: u64,
+    cgroup_limit_kb: Option<u64>,
+    cgroup_used_kb: Option<u64>,
+    max_capacity_kb: u64,
+    slots_used: u16,
+    slots_total: u16,
+    dimms: Vec<DIMMInfo>
+}
+#[derive(Debug, PartialEq)]
+pub struct DIMMInfo {
+    size_kb: u64,
+    memory_type: String,
+    speed_mts: u32,
+    manufacturer: String,
+    locator: String
+}
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryUsedDefinition {
+    Available,  // MemTotal - MemAvailable
+    Free,       // MemTotal - MemFree - Buffers - Cached - SReclaimable
+    Htop        // Same as free, but counting shared memory as used
 }
 #[derive(Deserialize)]
 pub struct MemoryConfiguration {
@@ -24,14 +55,35 @@ pub struct MemoryConfiguration {
     pub progress_empty: Option<String>,
     pub progress_target_length: Option<u8>,
     pub use_ibis: Option<bool>,
-    pub decimal_places: Option<u32>
+    pub decimal_places: Option<u32>,
+    pub used_definition: MemoryUsedDefinition,
+    pub use_cgroup_limits: bool,
+    pub newline_dimms: bool,
+    pub dimm_title: String,
+    pub dimm_format: String
 }
 impl Module for MemoryInfo {
     fn new() -> MemoryInfo {
         MemoryInfo {
             used_kb: 0,
             max_kb: 0,
-            percentage: 0.0
+            percentage: 0.0,
+            free_kb: 0,
+            available_kb: 0,
+            cached_kb: 0,
+            buffers_kb: 0,
+            shared_kb: 0,
+            dirty_kb: 0,
+            hugepages_kb: 0,
+            slab_kb: 0,
+            slab_reclaimable_kb: 0,
+            committed_kb: 0,
+            cgroup_limit_kb: None,
+            cgroup_used_kb: None,
+            max_capacity_kb: 0,
+            slots_used: 0,
+            slots_total: 0,
+            dimms: Vec::new()
         }
     }
 
@@ -56,7 +108,21 @@ impl Module for MemoryInfo {
             .replace("{used}", "Unknown")
             .replace("{max}", "Unknown")
             .replace("{bar}", "")
-            .replace("{percentage}", "Unknown");
+            .replace("{percentage}", "Unknown")
+            .replace("{free}", "Unknown")
+            .replace("{available}", "Unknown")
+            .replace("{cached}", "Unknown")
+            .replace("{buffers}", "Unknown")
+            .replace("{shared}", "Unknown")
+            .replace("{dirty}", "Unknown")
+            .replace("{hugepages}", "Unknown")
+            .replace("{slab}", "Unknown")
+            .replace("{committed}", "Unknown")
+            .replace("{cgroup_limit}", "Unknown")
+            .replace("{cgroup_used}", "Unknown")
+            .replace("{max_capacity}", "Unknown")
+            .replace("{slots_used}", "Unknown")
+            .replace("{slots_total}", "Unknown");
 
         Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
     }
@@ -75,63 +141,433 @@ impl Module for MemoryInfo {
             formatter::make_bar(&mut bar, left_border, right_border, progress, empty, self.percentage, length);
         }
 
+        // SMBIOS info may be missing entirely, e.g if we aren't root
+        let max_capacity: String = if self.max_capacity_kb == 0 {
+            "Unknown".to_string()
+        } else {
+            formatter::auto_format_bytes(self.max_capacity_kb, use_ibis, dec_places)
+        };
+        // No limit is normal, where as no usage means we couldn't find our cgroup at all
+        let cgroup_limit: String = match self.cgroup_limit_kb {
+            Some(r) => formatter::auto_format_bytes(r, use_ibis, dec_places),
+            None => "Unlimited".to_string(),
+        };
+        let cgroup_used: String = match self.cgroup_used_kb {
+            Some(r) => formatter::auto_format_bytes(r, use_ibis, dec_places),
+            None => "Unknown".to_string(),
+        };
+        let (slots_used, slots_total): (String, String) = if self.slots_total == 0 {
+            ("Unknown".to_string(), "Unknown".to_string())
+        } else {
+            (self.slots_used.to_string(), self.slots_total.to_string())
+        };
+
         #[allow(clippy::cast_possible_truncation)]
         formatter::process_percentage_placeholder(text, formatter::round(f64::from(self.percentage), dec_places) as f32, config)
             .replace("{used}", &formatter::auto_format_bytes(self.used_kb, use_ibis, dec_places))
             .replace("{max}", &formatter::auto_format_bytes(self.max_kb, use_ibis, dec_places))
             .replace("{bar}", &bar.to_string())
+            .replace("{free}", &formatter::auto_format_bytes(self.free_kb, use_ibis, dec_places))
+            .replace("{available}", &formatter::auto_format_bytes(self.available_kb, use_ibis, dec_places))
+            .replace("{cached}", &formatter::auto_format_bytes(self.cached_kb, use_ibis, dec_places))
+            .replace("{buffers}", &formatter::auto_format_bytes(self.buffers_kb, use_ibis, dec_places))
+            .replace("{shared}", &formatter::auto_format_bytes(self.shared_kb, use_ibis, dec_places))
+            .replace("{dirty}", &formatter::auto_format_bytes(self.dirty_kb, use_ibis, dec_places))
+            .replace("{hugepages}", &formatter::auto_format_bytes(self.hugepages_kb, use_ibis, dec_places))
+            .replace("{slab}", &formatter::auto_format_bytes(self.slab_kb, use_ibis, dec_places))
+            .replace("{committed}", &formatter::auto_format_bytes(self.committed_kb, use_ibis, dec_places))
+            .replace("{cgroup_limit}", &cgroup_limit)
+            .replace("{cgroup_used}", &cgroup_used)
+            .replace("{max_capacity}", &max_capacity)
+            .replace("{slots_used}", &slots_used)
+            .replace("{slots_total}", &slots_total)
+    }
+
+    fn gen_info_flags(format: &str) -> u32 {
+        let mut info_flags: u32 = 0;
+
+        if format.contains("{max_capacity}") || format.contains("{slots_used}") || format.contains("{slots_total}") {
+            info_flags |= MEMORY_INFOFLAG_SMBIOS;
+        }
+        if format.contains("{cgroup_limit}") || format.contains("{cgroup_used}") {
+            info_flags |= MEMORY_INFOFLAG_CGROUP;
+        }
+
+        info_flags
     }
+}
+impl MemoryInfo {
+    // Identical to the regular style method, but outputs a line per installed DIMM instead
+    pub fn style_dimms(&self, config: &Configuration) -> Vec<(String, String)> {
+        let title_color: &CrabFetchColor = config.memory.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.memory.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.memory.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.memory.separator.as_ref().unwrap_or(&config.separator);
+        let dec_places: u32 = config.memory.decimal_places.unwrap_or(config.decimal_places);
+        let use_ibis: bool = config.memory.use_ibis.unwrap_or(config.use_ibis);
+
+        let mut lines: Vec<(String, String)> = Vec::new();
+        for dimm in &self.dimms {
+            let replace = |text: &str| -> String {
+                text.replace("{size}", &formatter::auto_format_bytes(dimm.size_kb, use_ibis, dec_places))
+                    .replace("{type}", &dimm.memory_type)
+                    .replace("{speed}", &dimm.speed_mts.to_string())
+                    .replace("{manufacturer}", &dimm.manufacturer)
+                    .replace("{locator}", &dimm.locator)
+            };
+
+            let title: String = replace(&config.memory.dimm_title);
+            let value: String = self.replace_color_placeholders(&replace(&config.memory.dimm_format), config);
+            lines.push(Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value));
+        }
 
-    fn gen_info_flags(_: &str) -> u32 {
-        panic!("gen_info_flags called on memory module. This should never happen, please make a bug report!")
+        lines
     }
 }
 
+const MEMORY_INFOFLAG_SMBIOS: u32 = 1;
+const MEMORY_INFOFLAG_CGROUP: u32 = 2;
+
 // Clippy had a lot of issues with this function, while it's kinda not possible to improve it
 // without just being a pain in the ass to work with
 #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation, clippy::cast_precision_loss)]
-pub fn get_memory() -> Result<MemoryInfo, ModuleError> {
-    // no info flags here as while it would've had a slight benefit, all the info requires eachother anyway so
-    // it's hardly worth it
+pub fn get_memory(config: &Configuration) -> Result<MemoryInfo, ModuleError> {
     let mut memory: MemoryInfo = MemoryInfo::new();
+    let info_flags: u32 = MemoryInfo::gen_info_flags(&format!("{}{}", config.memory.format, config.memory.title));
 
     // Fetches from /proc/meminfo
+    // https://docs.kernel.org/filesystems/proc.html#meminfo
     let file: File = match File::open("/proc/meminfo") {
         Ok(r) => r,
         Err(e) => return Err(ModuleError::new("Memory", format!("Can't read from /proc/meminfo - {e}"))),
     };
 
-    let mut mem_available: u64 = 0;
+    let mut hugepages_total: u64 = 0;
+    let mut hugepage_size_kb: u64 = 0;
     let buffer: BufReader<File> = BufReader::new(file);
     for line in buffer.lines() {
         if line.is_err() {
             continue;
         }
         let line: String = line.unwrap();
-
-        if line.starts_with("MemTotal") {
-            let mut var: &str = line.split(": ").collect::<Vec<&str>>()[1];
-            var = var[..var.len() - 3].trim();
-            memory.max_kb = match var.to_string().parse::<f64>() {
-                Ok(r) => (r * 1.024) as u64,
-                Err(e) => return Err(ModuleError::new("Memory", format!("Could not parse total memory: {e}")))
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Everything is in KiB besides the hugepage counts, which have no unit
+        let value: &str = value.trim().trim_end_matches(" kB");
+        let value: f64 = match value.parse::<f64>() {
+            Ok(r) => r,
+            Err(e) => {
+                if key == "MemTotal" {
+                    return Err(ModuleError::new("Memory", format!("Could not parse total memory: {e}")));
+                }
+                continue;
             }
+        };
+        let value_kb: u64 = (value * 1.024) as u64;
+
+        match key {
+            "MemTotal" => memory.max_kb = value_kb,
+            "MemFree" => memory.free_kb = value_kb,
+            "MemAvailable" => memory.available_kb = value_kb,
+            "Buffers" => memory.buffers_kb = value_kb,
+            "Cached" => memory.cached_kb = value_kb,
+            "Shmem" => memory.shared_kb = value_kb,
+            "Dirty" => memory.dirty_kb = value_kb,
+            "Slab" => memory.slab_kb = value_kb,
+            "SReclaimable" => memory.slab_reclaimable_kb = value_kb,
+            "Committed_AS" => memory.committed_kb = value_kb,
+            "HugePages_Total" => hugepages_total = value as u64,
+            "Hugepagesize" => hugepage_size_kb = value_kb,
+            _ => {}
         }
-        if line.starts_with("MemAvailable") {
-            let mut var: &str = line.split(": ").collect::<Vec<&str>>()[1];
-            var = var[..var.len() - 3].trim();
-            mem_available = match var.to_string().parse::<f64>() {
-                Ok(r) => (r * 1.024) as u64,
-                Err(e) => return Err(ModuleError::new("Memory", format!("Could not parse memfree memory: {e}")))
-            }
+    }
+    memory.hugepages_kb = hugepages_total * hugepage_size_kb;
+
+    memory.used_kb = calc_used(&memory, &config.memory.used_definition);
+
+    // Container limits
+    if is_flag_set_u32(info_flags, MEMORY_INFOFLAG_CGROUP) || config.memory.use_cgroup_limits {
+        if let Some(cgroup) = cgroups::get_memory() {
+            memory.cgroup_limit_kb = cgroup.limit_kb;
+            memory.cgroup_used_kb = cgroup.usage_kb;
         }
-        if memory.max_kb != 0 && mem_available != 0 {
-            break;
+    }
+    if config.memory.use_cgroup_limits {
+        // Only when there's actually a limit below the system's memory, otherwise we'd just be
+        // showing the usage of our own process tree
+        if let Some(limit_kb) = memory.cgroup_limit_kb.filter(|x| *x < memory.max_kb) {
+            memory.max_kb = limit_kb;
+            if let Some(used_kb) = memory.cgroup_used_kb {
+                memory.used_kb = used_kb;
+            }
         }
     }
 
-    memory.used_kb = memory.max_kb - mem_available;
     memory.percentage = (memory.used_kb as f32 / memory.max_kb as f32) * 100.0;
 
+    // DIMM info from SMBIOS
+    // This is root only on most systems, so failing to read it is not an error, the placeholders
+    // will just show as unknown
+    if is_flag_set_u32(info_flags, MEMORY_INFOFLAG_SMBIOS) || config.memory.newline_dimms {
+        if let Ok(table) = smbios::read_table() {
+            fill_from_smbios(&mut memory, &smbios::parse_table(&table));
+        }
+    }
+
     Ok(memory)
 }
+
+// Each tool has their own idea of what "used" memory is, so it's left up to the user to choose
+fn calc_used(memory: &MemoryInfo, definition: &MemoryUsedDefinition) -> u64 {
+    match definition {
+        MemoryUsedDefinition::Available => memory.max_kb.saturating_sub(memory.available_kb),
+        // procps-ng's free prior to 4.0, which counts reclaimable slab as cache
+        MemoryUsedDefinition::Free => memory.max_kb
+            .saturating_sub(memory.free_kb)
+            .saturating_sub(memory.buffers_kb)
+            .saturating_sub(memory.cached_kb)
+            .saturating_sub(memory.slab_reclaimable_kb),
+        // htop's meter, which counts shared memory as used rather than cached
+        // https://github.com/htop-dev/htop/blob/main/linux/LinuxMachine.c
+        MemoryUsedDefinition::Htop => memory.max_kb
+            .saturating_sub(memory.free_kb)
+            .saturating_sub(memory.buffers_kb)
+            .saturating_sub((memory.cached_kb + memory.slab_reclaimable_kb).saturating_sub(memory.shared_kb)),
+    }
+}
+
+// SMBIOS structure types we care about
+const SMBIOS_TYPE_MEMORY_ARRAY: u8 = 16;
+const SMBIOS_TYPE_MEMORY_DEVICE: u8 = 17;
+// Memory array "use" for system memory, opposed to e.g video or flash memory
+const SMBIOS_MEMORY_ARRAY_USE_SYSTEM: u8 = 0x03;
+
+fn fill_from_smbios(memory: &mut MemoryInfo, structures: &[SMBIOSStructure]) {
+    // Type 16 - Physical Memory Array
+    // Only the system memory arrays are counted, as some boards describe flash/cache here too
+    let mut system_arrays: Vec<u16> = Vec::new();
+    for array in structures.iter().filter(|x| x.structure_type == SMBIOS_TYPE_MEMORY_ARRAY) {
+        if array.get_byte(0x05) != Some(SMBIOS_MEMORY_ARRAY_USE_SYSTEM) {
+            continue;
+        }
+        system_arrays.push(array.handle);
+
+        // In KiB, with 0x80000000 meaning it's too big and to use the extended capacity, in bytes
+        let capacity_kib: u64 = match array.get_dword(0x07) {
+            Some(0x8000_0000) => array.get_qword(0x0F).unwrap_or(0) / 1024,
+            Some(r) => u64::from(r),
+            None => 0,
+        };
+        memory.max_capacity_kb += capacity_kib * 1024 / 1000;
+    }
+
+    // Type 17 - Memory Device
+    for device in structures.iter().filter(|x| x.structure_type == SMBIOS_TYPE_MEMORY_DEVICE) {
+        if !system_arrays.is_empty() && !device.get_word(0x04).is_some_and(|x| system_arrays.contains(&x)) {
+            continue;
+        }
+        memory.slots_total += 1;
+
+        let Some(dimm) = parse_memory_device(device) else {
+            continue; // Empty slot
+        };
+        memory.slots_used += 1;
+        memory.dimms.push(dimm);
+    }
+}
+
+fn parse_memory_device(device: &SMBIOSStructure) -> Option<DIMMInfo> {
+    // 0 means no module is installed, 0xFFFF means it's unknown
+    // 0x7FFF means it's too big and to use the extended size, in MiB
+    // Otherwise bit 15 decides between KiB (1) or MiB (0)
+    let size_kib: u64 = match device.get_word(0x0C)? {
+        0 | 0xFFFF => return None,
+        0x7FFF => u64::from(device.get_dword(0x1C)? & 0x7FFF_FFFF) * 1024,
+        r if r & 0x8000 != 0 => u64::from(r & 0x7FFF),
+        r => u64::from(r) * 1024,
+    };
+
+    // Configured speed, backing up to the max speed for older tables that don't have it
+    // 0xFFFF means to use the extended field instead
+    let mut speed_mts: u32 = match device.get_word(0x20) {
+        Some(0xFFFF) => device.get_dword(0x58).unwrap_or(0),
+        Some(r) => u32::from(r),
+        None => 0,
+    };
+    if speed_mts == 0 {
+        speed_mts = match device.get_word(0x15) {
+            Some(0xFFFF) => device.get_dword(0x54).unwrap_or(0),
+            Some(r) => u32::from(r),
+            None => 0,
+        };
+    }
+
+    Some(DIMMInfo {
+        size_kb: size_kib * 1024 / 1000,
+        memory_type: memory_type_name(device.get_byte(0x12).unwrap_or(0x02)).to_string(),
+        speed_mts,
+        manufacturer: device.get_string(0x17).unwrap_or("Unknown".to_string()),
+        locator: device.get_string(0x10).unwrap_or("Unknown".to_string())
+    })
+}
+
+// 7.18.2 - Memory Device Type
+fn memory_type_name(id: u8) -> &'static str {
+    match id {
+        0x01 => "Other",
+        0x03 => "DRAM",
+        0x04 => "EDRAM",
+        0x05 => "VRAM",
+        0x06 => "SRAM",
+        0x07 => "RAM",
+        0x08 => "ROM",
+        0x09 => "Flash",
+        0x0A => "EEPROM",
+        0x0B => "FEPROM",
+        0x0C => "EPROM",
+        0x0D => "CDRAM",
+        0x0E => "3DRAM",
+        0x0F => "SDRAM",
+        0x10 => "SGRAM",
+        0x11 => "RDRAM",
+        0x12 => "DDR",
+        0x13 => "DDR2",
+        0x14 => "DDR2 FB-DIMM",
+        0x18 => "DDR3",
+        0x19 => "FBD2",
+        0x1A => "DDR4",
+        0x1B => "LPDDR",
+        0x1C => "LPDDR2",
+        0x1D => "LPDDR3",
+        0x1E => "LPDDR4",
+        0x1F => "Logical non-volatile device",
+        0x20 => "HBM",
+        0x21 => "HBM2",
+        0x22 => "DDR5",
+        0x23 => "LPDDR5",
+        0x24 => "HBM3",
+        _ => "Unknown"
+    }
+}
+
+mod tests {
+    // Builds a SMBIOS structure from it's formatted section and strings
+    #[allow(dead_code)]
+    fn build_structure(structure_type: u8, handle: u16, formatted: &[u8], strings: &[&str]) -> Vec<u8> {
+        let mut bytes: Vec<u8> = vec![structure_type, u8::try_from(formatted.len() + 4).unwrap()];
+        bytes.extend_from_slice(&handle.to_le_bytes());
+        bytes.extend_from_slice(formatted);
+        for string in strings {
+            bytes.extend_from_slice(string.as_bytes());
+            bytes.push(0);
+        }
+        if strings.is_empty() {
+            bytes.push(0);
+        }
+        bytes.push(0);
+        bytes
+    }
+
+    // A memory device (type 17) as a 3.3 table would lay it out, minus the header
+    #[allow(dead_code)]
+    fn build_memory_device(array_handle: u16, size: u16, extended_size: u32, memory_type: u8, speed: u16, configured_speed: u16) -> Vec<u8> {
+        let mut formatted: Vec<u8> = vec![0; 0x5C - 4];
+        let mut set = |offset: usize, bytes: &[u8]| formatted[offset - 4..offset - 4 + bytes.len()].copy_from_slice(bytes);
+        set(0x04, &array_handle.to_le_bytes());
+        set(0x0C, &size.to_le_bytes());
+        set(0x10, &[1]); // Device locator
+        set(0x11, &[2]); // Bank locator
+        set(0x12, &[memory_type]);
+        set(0x15, &speed.to_le_bytes());
+        set(0x17, &[3]); // Manufacturer
+        set(0x1C, &extended_size.to_le_bytes());
+        set(0x20, &configured_speed.to_le_bytes());
+        formatted
+    }
+
+    // Synthesised to look like a desktop with 2 of 4 slots filled, only containing the structures we read
+    #[allow(dead_code)]
+    fn build_fixture_table() -> Vec<u8> {
+        let mut table: Vec<u8> = Vec::new();
+        // Type 0 (BIOS) to check other structures are skipped over correctly
+        table.extend(build_structure(0, 0x0000, &[1, 2, 0x00, 0xF0, 3, 0xFF], &["American Megatrends International, LLC.", "1.A0", "06/14/2024"]));
+
+        // Type 16, system memory, 128 GiB max, 4 slots
+        let mut array: Vec<u8> = vec![0x03, 0x03, 0x03];
+        array.extend_from_slice(&(128 * 1024 * 1024_u32).to_le_bytes());
+        array.extend_from_slice(&0xFFFE_u16.to_le_bytes());
+        array.extend_from_slice(&4_u16.to_le_bytes());
+        array.extend_from_slice(&0_u64.to_le_bytes());
+        table.extend(build_structure(16, 0x0030, &array, &[]));
+
+        // Type 16, but flash memory, should be ignored along with it's devices
+        let mut flash: Vec<u8> = vec![0x03, 0x04, 0x03];
+        flash.extend_from_slice(&(16 * 1024_u32).to_le_bytes());
+        flash.extend_from_slice(&0xFFFE_u16.to_le_bytes());
+        flash.extend_from_slice(&1_u16.to_le_bytes());
+        flash.extend_from_slice(&0_u64.to_le_bytes());
+        table.extend(build_structure(16, 0x0040, &flash, &[]));
+
+        // Type 17's, 2 filled with DDR5 and 2 empty
+        table.extend(build_structure(17, 0x0031, &build_memory_device(0x0030, 0, 0, 0x02, 0, 0), &["DIMM 0", "P0 CHANNEL A", "Unknown"]));
+        table.extend(build_structure(17, 0x0032, &build_memory_device(0x0030, 16384, 0, 0x22, 6000, 5600), &["DIMM 1", "P0 CHANNEL A", "Kingston"]));
+        table.extend(build_structure(17, 0x0033, &build_memory_device(0x0030, 0, 0, 0x02, 0, 0), &["DIMM 0", "P0 CHANNEL B", "Unknown"]));
+        table.extend(build_structure(17, 0x0034, &build_memory_device(0x0030, 0x7FFF, 65536, 0x22, 6000, 0), &["DIMM 1", "P0 CHANNEL B", "Kingston"]));
+        table.extend(build_structure(17, 0x0041, &build_memory_device(0x0040, 16, 0, 0x09, 0, 0), &["FLASH", "", ""]));
+
+        // End of table
+        table.extend(build_structure(127, 0xFEFF, &[], &[]));
+        table
+    }
+
+    #[test]
+    fn test_smbios_structures() {
+        use crate::common_sources::smbios;
+
+        let table: Vec<u8> = build_fixture_table();
+        let structures = smbios::parse_table(&table);
+        assert_eq!(structures.len(), 8);
+        assert_eq!(structures[0].structure_type, 0);
+        assert_eq!(structures[0].get_string(0x04), Some("American Megatrends International, LLC.".to_string()));
+        assert_eq!(structures[0].get_string(0x05), Some("1.A0".to_string()));
+        assert_eq!(structures[0].get_string(0x08), Some("06/14/2024".to_string()));
+        assert_eq!(structures[1].handle, 0x0030);
+        assert_eq!(structures[1].get_word(0x0D), Some(4));
+        assert_eq!(structures[7].get_string(0x11), None);
+
+        // Garbage shouldn't panic
+        assert!(smbios::parse_table(&[17, 2, 0]).is_empty());
+        assert!(smbios::parse_table(&table[..table.len() / 2]).len() < 8);
+    }
+
+    #[test]
+    fn test_smbios_dimms() {
+        use crate::{common_sources::smbios, module::Module, modules::memory::{fill_from_smbios, DIMMInfo, MemoryInfo}};
+
+        let table: Vec<u8> = build_fixture_table();
+        let mut memory: MemoryInfo = MemoryInfo::new();
+        fill_from_smbios(&mut memory, &smbios::parse_table(&table));
+
+        assert_eq!(memory.max_capacity_kb, 128 * 1024 * 1024 * 1024 / 1000);
+        assert_eq!(memory.slots_total, 4);
+        assert_eq!(memory.slots_used, 2);
+        assert_eq!(memory.dimms, vec![
+            DIMMInfo {
+                size_kb: 16 * 1024 * 1024 * 1024 / 1000,
+                memory_type: "DDR5".to_string(),
+                speed_mts: 5600,
+                manufacturer: "Kingston".to_string(),
+                locator: "DIMM 1".to_string()
+            },
+            // Extended size, and configured speed backing up to the max speed
+            DIMMInfo {
+                size_kb: 64 * 1024 * 1024 * 1024 / 1000,
+                memory_type: "DDR5".to_string(),
+                speed_mts: 6000,
+                manufacturer: "Kingston".to_string(),
+                locator: "DIMM 1".to_string()
+            }
+        ]);
+    }
+}
@@ -14,7 +14,9 @@ pub mod shell;
 pub mod uptime;
 pub mod editor;
 pub mod locale;
+pub mod audio;
 pub mod battery;
+pub mod brightness;
 #[cfg(feature = "player")]
 pub mod player;
 pub mod initsys;
@@ -23,3 +25,13 @@ pub mod datetime;
 pub mod localip;
 pub mod theme;
 pub mod icon_theme;
+pub mod security_cpu;
+pub mod disks;
+pub mod pci;
+pub mod usb;
+pub mod diskio;
+pub mod firmware;
+pub mod motherboard;
+pub mod virtualization;
+pub mod network;
+pub mod netspeed;
@@ -0,0 +1,97 @@
+use serde::Deserialize;
+
+use crate::{common_sources::dmi, config_manager::Configuration, formatter::CrabFetchColor, module::Module, util::is_flag_set_u32, ModuleError};
+
+pub struct MotherboardInfo {
+    vendor: String,
+    name: String,
+    version: String
+}
+#[derive(Deserialize)]
+pub struct MotherboardConfiguration {
+    pub title: String,
+    pub title_color: Option<CrabFetchColor>,
+    pub title_bold: Option<bool>,
+    pub title_italic: Option<bool>,
+    pub separator: Option<String>,
+    pub format: String
+}
+impl Module for MotherboardInfo {
+    fn new() -> MotherboardInfo {
+        MotherboardInfo {
+            vendor: "Unknown".to_string(),
+            name: "Unknown".to_string(),
+            version: "Unknown".to_string()
+        }
+    }
+
+    fn style(&self, config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.motherboard.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.motherboard.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.motherboard.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.motherboard.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = self.replace_placeholders(&config.motherboard.title, config);
+        let value: String = self.replace_color_placeholders(&self.replace_placeholders(&config.motherboard.format, config), config);
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value)
+    }
+    fn unknown_output(config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.motherboard.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.motherboard.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.motherboard.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.motherboard.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = config.motherboard.title
+            .replace("{vendor}", "Unknown")
+            .replace("{name}", "Unknown")
+            .replace("{version}", "Unknown");
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
+    }
+
+    fn replace_placeholders(&self, text: &str, _: &Configuration) -> String {
+        text.replace("{vendor}", &self.vendor)
+            .replace("{name}", &self.name)
+            .replace("{version}", &self.version)
+    }
+
+    fn gen_info_flags(format: &str) -> u32 {
+        let mut info_flags: u32 = 0;
+
+        if format.contains("{vendor}") {
+            info_flags |= MOTHERBOARD_INFOFLAG_VENDOR;
+        }
+        if format.contains("{name}") {
+            info_flags |= MOTHERBOARD_INFOFLAG_NAME;
+        }
+        if format.contains("{version}") {
+            info_flags |= MOTHERBOARD_INFOFLAG_VERSION;
+        }
+
+        info_flags
+    }
+}
+
+const MOTHERBOARD_INFOFLAG_VENDOR: u32 = 1;
+const MOTHERBOARD_INFOFLAG_NAME: u32 = 2;
+const MOTHERBOARD_INFOFLAG_VERSION: u32 = 4;
+
+pub fn get_motherboard(config: &Configuration) -> Result<MotherboardInfo, ModuleError> {
+    let mut motherboard: MotherboardInfo = MotherboardInfo::new();
+    // title is tagged onto the end here to account for the title placeholders
+    let info_flags: u32 = MotherboardInfo::gen_info_flags(&format!("{}{}", config.motherboard.format, config.motherboard.title));
+
+    if is_flag_set_u32(info_flags, MOTHERBOARD_INFOFLAG_VENDOR) {
+        motherboard.vendor = dmi::read_value("board_vendor").map_err(|e| ModuleError::new("Motherboard", e))?;
+    }
+    if is_flag_set_u32(info_flags, MOTHERBOARD_INFOFLAG_NAME) {
+        motherboard.name = dmi::read_value("board_name").map_err(|e| ModuleError::new("Motherboard", e))?;
+    }
+    if is_flag_set_u32(info_flags, MOTHERBOARD_INFOFLAG_VERSION) {
+        motherboard.version = dmi::read_value("board_version").map_err(|e| ModuleError::new("Motherboard", e))?;
+    }
+
+    Ok(motherboard)
+}
+
@@ -1,5 +1,5 @@
 use std::{fs::{self, File}, io::{BufRead, BufReader, Error}, path::{Path, PathBuf}};
-use std::mem;
+use std::{cmp::Reverse, mem};
 
 #[cfg(feature = "android")]
 use std::env;
@@ -7,17 +7,38 @@ use std::env;
 use libc::statfs;
 use serde::Deserialize;
 
-use crate::{config_manager::Configuration, formatter::{self, CrabFetchColor}, module::Module, util::{self, is_flag_set_u32}, ModuleError};
+use crate::{config_manager::Configuration, disks, formatter::{self, CrabFetchColor}, module::Module, util::{self, is_flag_set_u32}, ModuleError};
 
 pub struct MountInfo {
     device: String,     // /dev/sda
     mount: String,      // /hdd
     filesystem: String,
-    space_avail_kb: u64,
+    disk_model: String,
+    space_used_kb: u64,
+    space_free_kb: u64,     // Includes the blocks reserved for root
+    space_avail_kb: u64,    // What unprivileged users can actually use
     space_total_kb: u64,
-    percent: f32
+    percent: f32,
+    inodes_free: u64,
+    inodes_total: u64,
+    options: String,    // rw,relatime,...
+    read_only: bool,
+    subvolume: Option<String>,      // btrfs only, e.g /@home
+    pool: Option<String>,           // ZFS only
+    dataset: Option<String>,        // ZFS only
+    volume_group: Option<String>,   // LVM only
+    logical_volume: Option<String>  // LVM only
 }
 #[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MountSortBy {
+    None,
+    Mount,
+    Size,
+    Usage
+}
+#[derive(Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct MountConfiguration {
     pub title: String,
     pub title_color: Option<CrabFetchColor>,
@@ -32,7 +53,16 @@ pub struct MountConfiguration {
     pub progress_target_length: Option<u8>,
     pub decimal_places: Option<u32>,
     pub use_ibis: Option<bool>,
-    pub ignore: Vec<String>
+    pub ignore: Vec<String>,
+    pub include_filesystems: Vec<String>,
+    pub exclude_filesystems: Vec<String>,
+    pub include_devices: Vec<String>,
+    pub exclude_devices: Vec<String>,
+    pub show_network: bool,
+    pub show_virtual: bool,
+    pub sort_by: MountSortBy,
+    pub df_compatible: bool,
+    pub deduplicate: bool
 }
 impl Module for MountInfo {
     fn new() -> MountInfo {
@@ -40,9 +70,21 @@ impl Module for MountInfo {
             device: "Unknown".to_string(),
             mount: "Unknown".to_string(),
             filesystem: "Unknown".to_string(),
+            disk_model: "Unknown".to_string(),
+            space_used_kb: 0,
+            space_free_kb: 0,
             space_avail_kb: 0,
             space_total_kb: 0,
-            percent: 0.0
+            percent: 0.0,
+            inodes_free: 0,
+            inodes_total: 0,
+            options: "Unknown".to_string(),
+            read_only: false,
+            subvolume: None,
+            pool: None,
+            dataset: None,
+            volume_group: None,
+            logical_volume: None
         }
     }
 
@@ -67,9 +109,23 @@ impl Module for MountInfo {
             .replace("{device}", "Unknown")
             .replace("{mount}", "Unknown")
             .replace("{filesystem}", "Unknown")
+            .replace("{disk_model}", "Unknown")
             .replace("{space_used}", "Unknown")
+            .replace("{space_free}", "Unknown")
             .replace("{space_avail}", "Unknown")
             .replace("{space_total}", "Unknown")
+            .replace("{inodes_used}", "Unknown")
+            .replace("{inodes_free}", "Unknown")
+            .replace("{inodes_total}", "Unknown")
+            .replace("{inodes_percent}", "Unknown")
+            .replace("{options}", "Unknown")
+            .replace("{read_only}", "Unknown")
+            .replace("{subvolume}", "Unknown")
+            .replace("{subvolume_name}", "Unknown")
+            .replace("{pool}", "Unknown")
+            .replace("{dataset}", "Unknown")
+            .replace("{volume_group}", "Unknown")
+            .replace("{logical_volume}", "Unknown")
             .replace("{bar}", " ");
 
         Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
@@ -94,9 +150,23 @@ impl Module for MountInfo {
             .replace("{device}", &self.device)
             .replace("{mount}", &self.mount)
             .replace("{filesystem}", &self.filesystem)
-            .replace("{space_used}", &formatter::auto_format_bytes(self.space_total_kb - self.space_avail_kb, use_ibis, dec_places))
+            .replace("{disk_model}", &self.disk_model)
+            .replace("{space_used}", &formatter::auto_format_bytes(self.space_used_kb, use_ibis, dec_places))
+            .replace("{space_free}", &formatter::auto_format_bytes(self.space_free_kb, use_ibis, dec_places))
             .replace("{space_avail}", &formatter::auto_format_bytes(self.space_avail_kb, use_ibis, dec_places))
             .replace("{space_total}", &formatter::auto_format_bytes(self.space_total_kb, use_ibis, dec_places))
+            .replace("{inodes_used}", &self.inodes_total.saturating_sub(self.inodes_free).to_string())
+            .replace("{inodes_free}", &self.inodes_free.to_string())
+            .replace("{inodes_total}", &self.inodes_total.to_string())
+            .replace("{inodes_percent}", &self.inodes_percent(dec_places))
+            .replace("{options}", &self.options)
+            .replace("{read_only}", if self.read_only {"Read-only"} else {"Read-write"})
+            .replace("{subvolume}", self.subvolume.as_deref().unwrap_or("None"))
+            .replace("{subvolume_name}", self.subvolume.as_deref().and_then(|x| x.rsplit('/').find(|x| !x.is_empty())).unwrap_or("None"))
+            .replace("{pool}", self.pool.as_deref().unwrap_or("None"))
+            .replace("{dataset}", self.dataset.as_deref().unwrap_or("None"))
+            .replace("{volume_group}", self.volume_group.as_deref().unwrap_or("None"))
+            .replace("{logical_volume}", self.logical_volume.as_deref().unwrap_or("None"))
             .replace("{bar}", &bar.to_string())
     }
 
@@ -109,12 +179,24 @@ impl Module for MountInfo {
         if format.contains("{space_used}") || format.contains("bar") {
             info_flags |= MOUNTS_INFOFLAG_SPACE_USED;
         }
+        if format.contains("{space_free}") {
+            info_flags |= MOUNTS_INFOFLAG_SPACE_FREE;
+        }
         if format.contains("{space_avail}") {
             info_flags |= MOUNTS_INFOFLAG_SPACE_AVAIL;
         }
         if format.contains("{space_total}") || format.contains("bar") {
             info_flags |= MOUNTS_INFOFLAG_SPACE_TOTAL;
         }
+        if format.contains("{disk_model}") {
+            info_flags |= MOUNTS_INFOFLAG_DISK_MODEL;
+        }
+        if format.contains("{inodes_") {
+            info_flags |= MOUNTS_INFOFLAG_INODES;
+        }
+        if format.contains("{volume_group}") || format.contains("{logical_volume}") {
+            info_flags |= MOUNTS_INFOFLAG_LVM;
+        }
 
         info_flags
     }
@@ -130,14 +212,82 @@ impl MountInfo {
             }
         }
 
+        if !config.mounts.include_filesystems.is_empty() && !config.mounts.include_filesystems.contains(&self.filesystem) {
+            return true
+        }
+        if config.mounts.exclude_filesystems.contains(&self.filesystem) {
+            return true
+        }
+        if !config.mounts.include_devices.is_empty() && !config.mounts.include_devices.iter().any(|x| self.device.starts_with(x)) {
+            return true
+        }
+        if config.mounts.exclude_devices.iter().any(|x| !x.is_empty() && self.device.starts_with(x)) {
+            return true
+        }
+
         false
     }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn inodes_percent(&self, dec_places: u32) -> String {
+        // Some filesystems (e.g btrfs) allocate inodes dynamically and report 0 here
+        if self.inodes_total == 0 {
+            return "Unknown".to_string();
+        }
+        let percent: f64 = self.inodes_total.saturating_sub(self.inodes_free) as f64 / self.inodes_total as f64 * 100.0;
+        format!("{}%", formatter::round(percent, dec_places))
+    }
 }
 
 const MOUNTS_INFOFLAG_DEVICE: u32 = 1;
+const MOUNTS_INFOFLAG_SPACE_FREE: u32 = 2;
 const MOUNTS_INFOFLAG_SPACE_USED: u32 = 4;
 const MOUNTS_INFOFLAG_SPACE_TOTAL: u32 = 8;
 const MOUNTS_INFOFLAG_SPACE_AVAIL: u32 = 16;
+const MOUNTS_INFOFLAG_DISK_MODEL: u32 = 32;
+const MOUNTS_INFOFLAG_INODES: u32 = 64;
+const MOUNTS_INFOFLAG_LVM: u32 = 128;
+
+// Filesystems mounted from another machine, only shown with show_network
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "fuse.sshfs",
+    "fuse.rclone",
+    "9p",
+    "ceph",
+    "glusterfs",
+    "davfs",
+    "afs"
+];
+// Kernel interfaces that are mounted as filesystems, but never hold anything worth showing, even
+// with show_virtual
+const PSEUDO_FILESYSTEMS: &[&str] = &[
+    "proc",
+    "sysfs",
+    "devpts",
+    "devtmpfs",
+    "cgroup",
+    "cgroup2",
+    "securityfs",
+    "debugfs",
+    "tracefs",
+    "pstore",
+    "bpf",
+    "mqueue",
+    "hugetlbfs",
+    "configfs",
+    "fusectl",
+    "efivarfs",
+    "binfmt_misc",
+    "autofs",
+    "selinuxfs",
+    "rpc_pipefs",
+    "nsfs"
+];
 
 pub fn get_mounted_drives(config: &Configuration) -> Result<Vec<MountInfo>, ModuleError> {
     let mut mounts: Vec<MountInfo> = Vec::new();
@@ -180,14 +330,39 @@ pub fn get_mounted_drives(config: &Configuration) -> Result<Vec<MountInfo>, Modu
             Some(r) => r,
             None => continue, // Invalid device or not a device we want
         };
+        let filesystem: &str = entries[2];
+        let is_network: bool = NETWORK_FILESYSTEMS.contains(&filesystem);
+        // ZFS datasets are named after their pool rather than a device, e.g tank/home
+        let is_physical: bool = !is_network && (filesystem == "zfs" || is_device_wanted(&mount.device));
+
+        if let Some(options) = entries.get(3) {
+            mount.options = (*options).to_string();
+            mount.read_only = options.split(',').any(|x| x == "ro");
+            if filesystem == "btrfs" {
+                mount.subvolume = options.split(',')
+                    .find_map(|x| x.strip_prefix("subvol="))
+                    .map(str::to_string);
+            }
+        }
+
         // skipped on android as we want fuse
+        // Virtual and network mounts all tend to share the same "device" (e.g tmpfs), so they're
+        // never de-duplicated
+        // btrfs subvolumes all share the same device, so they're told apart by the subvolume too
+        let device_key: String = match &mount.subvolume {
+            Some(subvolume) => format!("{}:{subvolume}", mount.device),
+            None => mount.device.clone(),
+        };
         #[cfg(not(feature = "android"))]
-        if device_cache.contains(&mount.device) {
+        if config.mounts.deduplicate && is_physical && device_cache.contains(&device_key) {
             continue; // Already processed
         }
-        device_cache.push(device_name.to_string());
+        device_cache.push(device_key);
 
-        if !is_device_wanted(&mount.device) {
+        if is_network && !config.mounts.show_network {
+            continue;
+        }
+        if !is_network && !is_physical && (!config.mounts.show_virtual || PSEUDO_FILESYSTEMS.contains(&filesystem)) {
             continue; // bullshit
         }
 
@@ -202,20 +377,53 @@ pub fn get_mounted_drives(config: &Configuration) -> Result<Vec<MountInfo>, Modu
             continue
         }
 
+        // Stacked mounts over the same point, which virtual ones like to do
+        if !is_physical && mounts.iter().any(|x| x.mount == mount_point) {
+            continue;
+        }
         mount.mount = mount_point.to_string();
-        mount.filesystem = entries[2].to_string();
+        mount.filesystem = filesystem.to_string();
         if mount.is_ignored(config) {
             continue;
         }
 
-        // statfs to get space data
-        if is_flag_set_u32(info_flags, MOUNTS_INFOFLAG_SPACE_AVAIL | MOUNTS_INFOFLAG_SPACE_USED | MOUNTS_INFOFLAG_SPACE_TOTAL) {
-            call_statfs(&mount_point, &mut mount)?;
+        if filesystem == "zfs" {
+            let (pool, dataset) = mount.device.split_once('/').unwrap_or((&mount.device, ""));
+            mount.pool = Some(pool.to_string());
+            if !dataset.is_empty() {
+                mount.dataset = Some(dataset.to_string());
+            }
+        }
+        if is_flag_set_u32(info_flags, MOUNTS_INFOFLAG_LVM) {
+            if let Some((volume_group, logical_volume)) = find_lvm_names(&mount.device) {
+                mount.volume_group = Some(volume_group);
+                mount.logical_volume = Some(logical_volume);
+            }
+        }
+
+        // statfs to get space and inode data
+        let needs_statfs: bool = matches!(config.mounts.sort_by, MountSortBy::Size | MountSortBy::Usage);
+        if needs_statfs || is_flag_set_u32(info_flags, MOUNTS_INFOFLAG_SPACE_FREE | MOUNTS_INFOFLAG_SPACE_AVAIL | MOUNTS_INFOFLAG_SPACE_USED | MOUNTS_INFOFLAG_SPACE_TOTAL | MOUNTS_INFOFLAG_INODES) {
+            call_statfs(&mount_point, &mut mount, config.mounts.df_compatible)?;
+        }
+
+        // Maps the partition back to the physical disk it lives on
+        if is_flag_set_u32(info_flags, MOUNTS_INFOFLAG_DISK_MODEL) {
+            if let Some(model) = disks::find_parent_disk(&mount.device).and_then(|x| disks::read_disk_model(&x)) {
+                mount.disk_model = model;
+            }
         }
 
         mounts.push(mount);
     }
 
+    match config.mounts.sort_by {
+        MountSortBy::None => {},
+        MountSortBy::Mount => mounts.sort_by(|a, b| a.mount.cmp(&b.mount)),
+        MountSortBy::Size => mounts.sort_by_key(|x| Reverse(x.space_total_kb)),
+        MountSortBy::Usage => mounts.sort_by(|a, b| b.percent.total_cmp(&a.percent)),
+    }
+
     Ok(mounts)
 }
 
@@ -223,7 +431,7 @@ pub fn get_mounted_drives(config: &Configuration) -> Result<Vec<MountInfo>, Modu
 // pasted i swear)
 // https://github.com/GuillaumeGomez/sysinfo/blob/master/src/unix/linux/disk.rs#L96
 #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation, clippy::cast_precision_loss)]
-fn call_statfs(path: &str, mount: &mut MountInfo) -> Result<(), ModuleError> {
+fn call_statfs(path: &str, mount: &mut MountInfo, df_compatible: bool) -> Result<(), ModuleError> {
     let mut bytes: Vec<u8> = path.as_bytes().to_vec();
     bytes.push(0);
     unsafe { // spooky
@@ -237,13 +445,67 @@ fn call_statfs(path: &str, mount: &mut MountInfo) -> Result<(), ModuleError> {
             return Err(ModuleError::new("Mounts", format!("'statfs' syscall failed for mount point {path} (code {c})")))
         }
 
-        mount.space_total_kb = (buffer.f_blocks * buffer.f_bsize as u64) / 1000;
-        mount.space_avail_kb = (buffer.f_bfree * buffer.f_bsize as u64) / 1000;
-        mount.percent = ((((mount.space_total_kb - mount.space_avail_kb) as f64) / mount.space_total_kb as f64) * 100.0) as f32;
+        // Sizes are in f_frsize units, which is the same as f_bsize on Linux
+        // KB here is 1000 bytes, as auto_format_bytes expects
+        let block_size: u64 = buffer.f_frsize as u64;
+        mount.space_total_kb = (buffer.f_blocks * block_size) / 1000;
+        mount.space_free_kb = (buffer.f_bfree * block_size) / 1000;
+        mount.space_avail_kb = (buffer.f_bavail * block_size) / 1000;
+        mount.space_used_kb = mount.space_total_kb.saturating_sub(mount.space_free_kb);
+
+        if df_compatible {
+            // df leaves the reserved blocks out of the percentage entirely and rounds up
+            // https://git.savannah.gnu.org/cgit/coreutils.git/tree/src/df.c
+            let usable_kb: u64 = mount.space_used_kb + mount.space_avail_kb;
+            if usable_kb != 0 {
+                mount.percent = ((mount.space_used_kb as f64 / usable_kb as f64) * 100.0).ceil() as f32;
+            }
+        } else if mount.space_total_kb != 0 {
+            mount.percent = ((mount.space_used_kb as f64 / mount.space_total_kb as f64) * 100.0) as f32;
+        }
+        mount.inodes_total = buffer.f_files as u64;
+        mount.inodes_free = buffer.f_ffree as u64;
     }
     Ok(())
 }
 
+// Finds the volume group and logical volume a device mapper device belongs to
+fn find_lvm_names(device: &str) -> Option<(String, String)> {
+    // /dev/mapper/vg-lv is a symlink to the real /dev/dm-X device
+    let real_path: PathBuf = fs::canonicalize(device).ok()?;
+    let name: &str = real_path.file_name()?.to_str()?;
+    if !name.starts_with("dm-") {
+        return None;
+    }
+
+    // Other device mapper users such as LUKS use the same naming, so make sure this is LVM
+    let dm_path: PathBuf = Path::new("/sys/block").join(name).join("dm");
+    if !util::file_read(&dm_path.join("uuid")).ok()?.starts_with("LVM-") {
+        return None;
+    }
+    let dm_name: String = util::file_read(&dm_path.join("name")).ok()?;
+    split_dm_name(dm_name.trim())
+}
+
+// Splits a LVM device mapper name, e.g "vg0-root", into it's volume group and logical volume
+// Any dashes inside either name are escaped by LVM as a double dash
+fn split_dm_name(name: &str) -> Option<(String, String)> {
+    let bytes: &[u8] = name.as_bytes();
+    let mut i: usize = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'-' {
+            if bytes.get(i + 1) == Some(&b'-') {
+                i += 2;
+                continue;
+            }
+            return Some((name[..i].replace("--", "-"), name[i + 1..].replace("--", "-")));
+        }
+        i += 1;
+    }
+
+    None
+}
+
 fn get_device_name(device_name: &str) -> Option<String> {
     // This method is also responsible for filtering out any devices we don't want
     let dev: String;
@@ -0,0 +1,164 @@
+use std::{fs::{self, ReadDir}, path::{Path, PathBuf}, thread, time::{Duration, Instant}};
+
+use serde::Deserialize;
+
+use crate::{config_manager::Configuration, formatter::{self, CrabFetchColor}, module::Module, network, util, ModuleError};
+
+pub struct NetSpeedInfo {
+    name: String,
+    rx_kb_per_sec: f64,
+    tx_kb_per_sec: f64,
+    rx_total_kb: u64,
+    tx_total_kb: u64
+}
+#[derive(Deserialize)]
+pub struct NetSpeedConfiguration {
+    pub title: String,
+    pub title_color: Option<CrabFetchColor>,
+    pub title_bold: Option<bool>,
+    pub title_italic: Option<bool>,
+    pub separator: Option<String>,
+    pub format: String,
+    pub decimal_places: Option<u32>,
+    pub use_ibis: Option<bool>,
+    pub interval_ms: u64,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>
+}
+impl Module for NetSpeedInfo {
+    fn new() -> NetSpeedInfo {
+        NetSpeedInfo {
+            name: "Unknown".to_string(),
+            rx_kb_per_sec: 0.0,
+            tx_kb_per_sec: 0.0,
+            rx_total_kb: 0,
+            tx_total_kb: 0
+        }
+    }
+
+    fn style(&self, config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.netspeed.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.netspeed.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.netspeed.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.netspeed.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = self.replace_placeholders(&config.netspeed.title, config);
+        let value: String = self.replace_color_placeholders(&self.replace_placeholders(&config.netspeed.format, config), config);
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value)
+    }
+    fn unknown_output(config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.netspeed.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.netspeed.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.netspeed.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.netspeed.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = config.netspeed.title
+            .replace("{name}", "Unknown")
+            .replace("{rx}", "Unknown")
+            .replace("{tx}", "Unknown")
+            .replace("{rx_total}", "Unknown")
+            .replace("{tx_total}", "Unknown");
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
+    }
+
+    fn replace_placeholders(&self, text: &str, config: &Configuration) -> String {
+        let dec_places: u32 = config.netspeed.decimal_places.unwrap_or(config.decimal_places);
+        let use_ibis: bool = config.netspeed.use_ibis.unwrap_or(config.use_ibis);
+
+        text.replace("{name}", &self.name)
+            .replace("{rx}", &formatter::auto_format_bytes_f64(self.rx_kb_per_sec, use_ibis, dec_places))
+            .replace("{tx}", &formatter::auto_format_bytes_f64(self.tx_kb_per_sec, use_ibis, dec_places))
+            .replace("{rx_total}", &formatter::auto_format_bytes(self.rx_total_kb, use_ibis, dec_places))
+            .replace("{tx_total}", &formatter::auto_format_bytes(self.tx_total_kb, use_ibis, dec_places))
+    }
+
+    fn gen_info_flags(_: &str) -> u32 {
+        panic!("gen_info_flags called on netspeed module. This should never happen, please make a bug report!")
+    }
+}
+
+// Byte counters for an interface at a single point in time
+struct NetSample {
+    name: String,
+    path: PathBuf,
+    rx_bytes: u64,
+    tx_bytes: u64
+}
+
+pub fn get_netspeed(config: &Configuration) -> Result<Vec<NetSpeedInfo>, ModuleError> {
+    let mut interfaces: Vec<NetSpeedInfo> = Vec::new();
+
+    // Find the interfaces we care about, and take our first sample
+    let dir: ReadDir = match fs::read_dir("/sys/class/net") {
+        Ok(r) => r,
+        Err(e) => return Err(ModuleError::new("NetSpeed", format!("Can't read from /sys/class/net: {e}"))),
+    };
+    let mut samples: Vec<NetSample> = Vec::new();
+    for entry in dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let name: String = entry.file_name().to_string_lossy().to_string();
+        let path: PathBuf = entry.path();
+
+        let interface_type: String = network::find_interface_type(&path);
+        if !config.netspeed.include.is_empty() && !config.netspeed.include.iter().any(|x| *x == name || *x == interface_type) {
+            continue;
+        }
+        if config.netspeed.exclude.iter().any(|x| *x == name || *x == interface_type) {
+            continue;
+        }
+
+        let (rx_bytes, tx_bytes) = read_byte_counters(&path)?;
+        samples.push(NetSample {
+            name,
+            path,
+            rx_bytes,
+            tx_bytes
+        });
+    }
+    let start: Instant = Instant::now();
+
+    // Then wait and sample again, using the real time that passed in case we got held up
+    thread::sleep(Duration::from_millis(config.netspeed.interval_ms));
+    let elapsed_ms: f64 = (start.elapsed().as_secs_f64() * 1000.0).max(1.0);
+    for sample in samples {
+        // The interface may have gone away in the meantime, e.g a VPN or USB tether disconnecting
+        let Ok((rx_bytes, tx_bytes)) = read_byte_counters(&sample.path) else {
+            continue;
+        };
+
+        // bytes per ms is the same as kilobytes per second
+        #[allow(clippy::cast_precision_loss)]
+        let (rx_kb_per_sec, tx_kb_per_sec): (f64, f64) = (
+            rx_bytes.saturating_sub(sample.rx_bytes) as f64 / elapsed_ms,
+            tx_bytes.saturating_sub(sample.tx_bytes) as f64 / elapsed_ms
+        );
+        interfaces.push(NetSpeedInfo {
+            name: sample.name,
+            rx_kb_per_sec,
+            tx_kb_per_sec,
+            rx_total_kb: rx_bytes / 1000,
+            tx_total_kb: tx_bytes / 1000
+        });
+    }
+
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(interfaces)
+}
+
+fn read_byte_counters(path: &Path) -> Result<(u64, u64), ModuleError> {
+    // https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-net-statistics
+    let mut counters: [u64; 2] = [0; 2];
+    for (i, file) in ["statistics/rx_bytes", "statistics/tx_bytes"].iter().enumerate() {
+        let path: PathBuf = path.join(file);
+        counters[i] = match util::file_read(&path) {
+            Ok(r) => r.trim().parse::<u64>().unwrap_or(0),
+            Err(e) => return Err(ModuleError::new("NetSpeed", format!("Can't read from {} - {e}", path.display()))),
+        };
+    }
+
+    Ok((counters[0], counters[1]))
+}
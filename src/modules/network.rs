@@ -0,0 +1,244 @@
+use std::{fs::{self, ReadDir}, path::{Path, PathBuf}};
+
+use serde::Deserialize;
+
+use crate::{common_sources::syscalls::{InterfaceAddress, SyscallCache}, config_manager::Configuration, formatter::CrabFetchColor, module::Module, util::{self, is_flag_set_u32}, ModuleError};
+
+pub struct NetworkInfo {
+    name: String,
+    state: String,              // up / down / dormant / ...
+    speed_mbps: Option<u32>,
+    duplex: String,
+    mtu: u32,
+    mac: String,
+    driver: String,
+    interface_type: String,     // ethernet / wifi / bridge / tun / wireguard / ...
+    addresses: Vec<String>      // CIDR notation
+}
+#[derive(Deserialize)]
+pub struct NetworkConfiguration {
+    pub title: String,
+    pub title_color: Option<CrabFetchColor>,
+    pub title_bold: Option<bool>,
+    pub title_italic: Option<bool>,
+    pub separator: Option<String>,
+    pub format: String,
+    pub show_mac: bool,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>
+}
+impl Module for NetworkInfo {
+    fn new() -> NetworkInfo {
+        NetworkInfo {
+            name: "Unknown".to_string(),
+            state: "Unknown".to_string(),
+            speed_mbps: None,
+            duplex: "Unknown".to_string(),
+            mtu: 0,
+            mac: "Unknown".to_string(),
+            driver: "Unknown".to_string(),
+            interface_type: "Unknown".to_string(),
+            addresses: Vec::new()
+        }
+    }
+
+    fn style(&self, config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.network.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.network.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.network.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.network.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = self.replace_placeholders(&config.network.title, config);
+        let value: String = self.replace_color_placeholders(&self.replace_placeholders(&config.network.format, config), config);
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value)
+    }
+    fn unknown_output(config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.network.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.network.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.network.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.network.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = config.network.title
+            .replace("{name}", "Unknown")
+            .replace("{state}", "Unknown")
+            .replace("{speed}", "Unknown")
+            .replace("{duplex}", "Unknown")
+            .replace("{mtu}", "Unknown")
+            .replace("{mac}", "Unknown")
+            .replace("{driver}", "Unknown")
+            .replace("{type}", "Unknown")
+            .replace("{addresses}", "Unknown");
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
+    }
+
+    fn replace_placeholders(&self, text: &str, _: &Configuration) -> String {
+        let speed: String = match self.speed_mbps {
+            Some(r) => format!("{r} Mb/s"),
+            None => "Unknown".to_string(),
+        };
+        let addresses: String = if self.addresses.is_empty() {
+            "None".to_string()
+        } else {
+            self.addresses.join(", ")
+        };
+
+        text.replace("{name}", &self.name)
+            .replace("{state}", &self.state)
+            .replace("{speed}", &speed)
+            .replace("{duplex}", &self.duplex)
+            .replace("{mtu}", &self.mtu.to_string())
+            .replace("{mac}", &self.mac)
+            .replace("{driver}", &self.driver)
+            .replace("{type}", &self.interface_type)
+            .replace("{addresses}", &addresses)
+    }
+
+    fn gen_info_flags(format: &str) -> u32 {
+        let mut info_flags: u32 = 0;
+
+        if format.contains("{speed}") || format.contains("{duplex}") {
+            info_flags |= NETWORK_INFOFLAG_LINK;
+        }
+        if format.contains("{mac}") {
+            info_flags |= NETWORK_INFOFLAG_MAC;
+        }
+        if format.contains("{driver}") {
+            info_flags |= NETWORK_INFOFLAG_DRIVER;
+        }
+        if format.contains("{addresses}") {
+            info_flags |= NETWORK_INFOFLAG_ADDRESSES;
+        }
+
+        info_flags
+    }
+}
+
+const NETWORK_INFOFLAG_LINK: u32 = 1;
+const NETWORK_INFOFLAG_MAC: u32 = 2;
+const NETWORK_INFOFLAG_DRIVER: u32 = 4;
+const NETWORK_INFOFLAG_ADDRESSES: u32 = 8;
+
+pub fn get_network(config: &Configuration, syscall_cache: &mut SyscallCache) -> Result<Vec<NetworkInfo>, ModuleError> {
+    let mut interfaces: Vec<NetworkInfo> = Vec::new();
+    // title is tagged onto the end here to account for the title placeholders
+    let info_flags: u32 = NetworkInfo::gen_info_flags(&format!("{}{}", config.network.format, config.network.title));
+
+    let mut interface_addrs: Vec<InterfaceAddress> = Vec::new();
+    if is_flag_set_u32(info_flags, NETWORK_INFOFLAG_ADDRESSES) {
+        interface_addrs = match syscall_cache.get_ifaddrs_cached() {
+            Ok(r) => r,
+            Err(e) => return Err(ModuleError::new("Network", e)),
+        };
+    }
+
+    // https://www.kernel.org/doc/Documentation/ABI/testing/sysfs-class-net
+    let dir: ReadDir = match fs::read_dir("/sys/class/net") {
+        Ok(r) => r,
+        Err(e) => return Err(ModuleError::new("Network", format!("Can't read from /sys/class/net: {e}"))),
+    };
+    for entry in dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let name: String = entry.file_name().to_string_lossy().to_string();
+        let path: PathBuf = entry.path();
+
+        let mut interface: NetworkInfo = NetworkInfo::new();
+        interface.interface_type = find_interface_type(&path);
+        if !config.network.include.is_empty() && !config.network.include.iter().any(|x| *x == name || *x == interface.interface_type) {
+            continue;
+        }
+        if config.network.exclude.iter().any(|x| *x == name || *x == interface.interface_type) {
+            continue;
+        }
+
+        // Left as unknown if the interface went away mid scan, rather than hiding all the others
+        if let Ok(r) = util::file_read(&path.join("operstate")) {
+            interface.state = r.trim().to_string();
+        }
+        interface.mtu = util::file_read(&path.join("mtu")).ok()
+            .and_then(|x| x.trim().parse::<u32>().ok())
+            .unwrap_or(0);
+
+        if is_flag_set_u32(info_flags, NETWORK_INFOFLAG_LINK) {
+            // Reading these fails with EINVAL when the link is down, or gives -1 for virtual
+            // interfaces that have no real speed
+            interface.speed_mbps = util::file_read(&path.join("speed")).ok()
+                .and_then(|x| x.trim().parse::<u32>().ok())
+                .filter(|x| *x != 0);
+            if let Ok(duplex) = util::file_read(&path.join("duplex")) {
+                interface.duplex = duplex.trim().to_string();
+            }
+        }
+        if is_flag_set_u32(info_flags, NETWORK_INFOFLAG_MAC) {
+            if config.network.show_mac {
+                if let Ok(mac) = util::file_read(&path.join("address")) {
+                    interface.mac = mac.trim().to_string();
+                }
+            } else {
+                interface.mac = "Hidden".to_string();
+            }
+        }
+        if is_flag_set_u32(info_flags, NETWORK_INFOFLAG_DRIVER) {
+            // Virtual interfaces aren't backed by a device, so have no driver
+            if let Ok(driver) = fs::read_link(path.join("device/driver")) {
+                if let Some(driver) = driver.file_name() {
+                    interface.driver = driver.to_string_lossy().to_string();
+                }
+            }
+        }
+        if is_flag_set_u32(info_flags, NETWORK_INFOFLAG_ADDRESSES) {
+            interface.addresses = interface_addrs.iter()
+                .filter(|x| x.interface == name)
+                .map(|x| match x.prefix_length() {
+                    Some(r) => format!("{}/{r}", x.addr),
+                    None => x.addr.to_string()
+                })
+                .collect();
+        }
+
+        interface.name = name;
+        interfaces.push(interface);
+    }
+
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(interfaces)
+}
+
+// Works out what kind of interface this is from it's directory in /sys/class/net
+pub fn find_interface_type(path: &Path) -> String {
+    // ARPHRD_* values from include/uapi/linux/if_arp.h
+    let arp_type: String = util::file_read(&path.join("type")).unwrap_or_default();
+    // Most virtual interfaces say what they are in here
+    let uevent: String = util::file_read(&path.join("uevent")).unwrap_or_default();
+    let devtype: Option<&str> = uevent.lines().find_map(|x| x.strip_prefix("DEVTYPE="));
+
+    let interface_type: &str = if arp_type.trim() == "772" {
+        "loopback"
+    } else if devtype == Some("wlan") || path.join("wireless").exists() || path.join("phy80211").exists() {
+        "wifi"
+    } else if devtype == Some("bridge") || path.join("bridge").exists() {
+        "bridge"
+    } else if devtype == Some("wireguard") {
+        "wireguard"
+    } else if devtype == Some("bond") || path.join("bonding").exists() {
+        "bond"
+    } else if devtype == Some("vlan") {
+        "vlan"
+    } else if path.join("tun_flags").exists() || arp_type.trim() == "65534" {
+        "tun"
+    } else if arp_type.trim() == "1" {
+        // Anything else pretending to be ethernet without hardware behind it, e.g veth
+        if path.join("device").exists() {
+            "ethernet"
+        } else {
+            "virtual"
+        }
+    } else {
+        "other"
+    };
+
+    interface_type.to_string()
+}
@@ -0,0 +1,145 @@
+use std::fs;
+
+use serde::Deserialize;
+
+use crate::{common_sources::{hwids, ids_index::IdsIndex, pci::{self, PCIDevice}}, config_manager::Configuration, formatter::CrabFetchColor, module::Module, util::is_flag_set_u32, ModuleError};
+
+pub struct PCIInfo {
+    slot: String,       // 0000:00:1f.3
+    class: String,      // Audio device
+    vendor: String,
+    device: String,
+    driver: String
+}
+#[derive(Deserialize)]
+pub struct PCIConfiguration {
+    pub title: String,
+    pub title_color: Option<CrabFetchColor>,
+    pub title_bold: Option<bool>,
+    pub title_italic: Option<bool>,
+    pub separator: Option<String>,
+    pub format: String,
+    pub include: Vec<String>,
+    pub exclude: Vec<String>
+}
+impl Module for PCIInfo {
+    fn new() -> PCIInfo {
+        PCIInfo {
+            slot: "Unknown".to_string(),
+            class: "Unknown".to_string(),
+            vendor: "Unknown".to_string(),
+            device: "Unknown".to_string(),
+            driver: "Unknown".to_string()
+        }
+    }
+
+    fn style(&self, config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.pci.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.pci.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.pci.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.pci.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = self.replace_placeholders(&config.pci.title, config);
+        let value: String = self.replace_color_placeholders(&self.replace_placeholders(&config.pci.format, config), config);
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value)
+    }
+    fn unknown_output(config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.pci.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.pci.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.pci.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.pci.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = config.pci.title
+            .replace("{slot}", "Unknown")
+            .replace("{class}", "Unknown")
+            .replace("{vendor}", "Unknown")
+            .replace("{device}", "Unknown")
+            .replace("{driver}", "Unknown");
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
+    }
+
+    fn replace_placeholders(&self, text: &str, _: &Configuration) -> String {
+        text.replace("{slot}", &self.slot)
+            .replace("{class}", &self.class)
+            .replace("{vendor}", &self.vendor)
+            .replace("{device}", &self.device)
+            .replace("{driver}", &self.driver)
+    }
+
+    fn gen_info_flags(format: &str) -> u32 {
+        let mut info_flags: u32 = 0;
+
+        if format.contains("{class}") {
+            info_flags |= PCI_INFOFLAG_CLASS;
+        }
+        if format.contains("{vendor}") || format.contains("{device}") {
+            info_flags |= PCI_INFOFLAG_NAMES;
+        }
+        if format.contains("{driver}") {
+            info_flags |= PCI_INFOFLAG_DRIVER;
+        }
+
+        info_flags
+    }
+}
+
+const PCI_INFOFLAG_CLASS: u32 = 1;
+const PCI_INFOFLAG_NAMES: u32 = 2;
+const PCI_INFOFLAG_DRIVER: u32 = 4;
+
+pub fn get_pci_devices(config: &Configuration) -> Result<Vec<PCIInfo>, ModuleError> {
+    let mut devices: Vec<PCIInfo> = Vec::new();
+    // title is tagged onto the end here to account for the title placeholders
+    let info_flags: u32 = PCIInfo::gen_info_flags(&format!("{}{}", config.pci.format, config.pci.title));
+
+    // Opened once up front, rather than for every device
+    let ids: Option<IdsIndex> = match hwids::find_pci_ids(config.pci_ids_path.as_deref()).map(|x| hwids::open_ids(&x)) {
+        Some(Ok(r)) => Some(r),
+        Some(Err(e)) => return Err(ModuleError::new("PCI", e)),
+        None => None,
+    };
+
+    let pci_devices: Vec<PCIDevice> = match pci::list_devices() {
+        Ok(r) => r,
+        Err(e) => return Err(ModuleError::new("PCI", e)),
+    };
+    for pci_device in pci_devices {
+        let class_key: &str = pci::class_key(&pci_device.class, &pci_device.subclass);
+        if !config.pci.include.is_empty() && !config.pci.include.iter().any(|x| x == class_key) {
+            continue;
+        }
+        if config.pci.exclude.iter().any(|x| x == class_key) {
+            continue;
+        }
+
+        let mut device: PCIInfo = PCIInfo::new();
+        if is_flag_set_u32(info_flags, PCI_INFOFLAG_CLASS) {
+            device.class = ids.as_ref().and_then(|x| hwids::search_pci_class(x, &pci_device.class, &pci_device.subclass))
+                .unwrap_or(pci::class_name(&pci_device.class).to_string());
+        }
+        if is_flag_set_u32(info_flags, PCI_INFOFLAG_NAMES) {
+            (device.vendor, device.device) = match &ids {
+                Some(ids) => hwids::search_ids(ids, &pci_device.vendor_id, &pci_device.device_id),
+                None => hwids::fallback_pci_names(&pci_device.vendor_id, &pci_device.device_id, None),
+            };
+            if device.vendor.is_empty() {
+                device.vendor.clone_from(&pci_device.vendor_id);
+            }
+        }
+        if is_flag_set_u32(info_flags, PCI_INFOFLAG_DRIVER) {
+            // The driver is a symlink to it's directory in /sys/bus/pci/drivers
+            if let Some(driver) = fs::read_link(pci_device.path.join("driver")).ok().and_then(|x| Some(x.file_name()?.to_str()?.to_string())) {
+                device.driver = driver;
+            } else {
+                device.driver = "None".to_string();
+            }
+        }
+
+        device.slot = pci_device.slot;
+        devices.push(device);
+    }
+
+    Ok(devices)
+}
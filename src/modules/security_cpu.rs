@@ -0,0 +1,153 @@
+use std::{fs::{self, ReadDir}, path::Path};
+
+use serde::Deserialize;
+
+use crate::{config_manager::Configuration, formatter::CrabFetchColor, module::Module, util, ModuleError};
+
+pub struct SecurityCPUInfo {
+    entries: Vec<VulnerabilityEntry>,
+    vulnerable: u16,
+    mitigated: u16,
+    not_affected: u16,
+}
+pub struct VulnerabilityEntry {
+    name: String,   // spectre_v2
+    status: String, // Mitigation: Retpolines; ...
+}
+impl VulnerabilityEntry {
+    // Anything that mentions being vulnerable counts, as some mitigations only partially cover
+    // the issue, e.g "Mitigation: Enhanced IBRS; BHI: Vulnerable"
+    pub fn is_vulnerable(&self) -> bool {
+        self.status.contains("Vulnerable")
+    }
+}
+#[derive(Deserialize)]
+pub struct SecurityCPUConfiguration {
+    pub title: String,
+    pub title_color: Option<CrabFetchColor>,
+    pub title_bold: Option<bool>,
+    pub title_italic: Option<bool>,
+    pub separator: Option<String>,
+    pub format: String,
+    pub list_entries: bool,
+    pub entry_title: String,
+    pub entry_format: String,
+    pub vulnerable_format: String,
+    pub ignore: Vec<String>
+}
+impl Module for SecurityCPUInfo {
+    fn new() -> SecurityCPUInfo {
+        SecurityCPUInfo {
+            entries: Vec::new(),
+            vulnerable: 0,
+            mitigated: 0,
+            not_affected: 0
+        }
+    }
+
+    fn style(&self, config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.security_cpu.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.security_cpu.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.security_cpu.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.security_cpu.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = self.replace_placeholders(&config.security_cpu.title, config);
+        let value: String = self.replace_color_placeholders(&self.replace_placeholders(&config.security_cpu.format, config), config);
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value)
+    }
+    fn unknown_output(config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.security_cpu.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.security_cpu.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.security_cpu.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.security_cpu.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = config.security_cpu.title
+            .replace("{vulnerable}", "Unknown")
+            .replace("{mitigated}", "Unknown")
+            .replace("{not_affected}", "Unknown")
+            .replace("{total}", "Unknown");
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
+    }
+
+    fn replace_placeholders(&self, text: &str, _: &Configuration) -> String {
+        text.replace("{vulnerable}", &self.vulnerable.to_string())
+            .replace("{mitigated}", &self.mitigated.to_string())
+            .replace("{not_affected}", &self.not_affected.to_string())
+            .replace("{total}", &self.entries.len().to_string())
+    }
+
+    fn gen_info_flags(_: &str) -> u32 {
+        panic!("gen_info_flags called on security_cpu module. This should never happen, please make a bug report!")
+    }
+}
+impl SecurityCPUInfo {
+    // Used when list_entries is on, styles each vulnerability onto it's own line
+    pub fn style_entries(&self, config: &Configuration) -> Vec<(String, String)> {
+        let title_color: &CrabFetchColor = config.security_cpu.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.security_cpu.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.security_cpu.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.security_cpu.separator.as_ref().unwrap_or(&config.separator);
+
+        let mut lines: Vec<(String, String)> = Vec::new();
+        for entry in &self.entries {
+            let format: &str = if entry.is_vulnerable() {
+                &config.security_cpu.vulnerable_format
+            } else {
+                &config.security_cpu.entry_format
+            };
+
+            let title: String = config.security_cpu.entry_title
+                .replace("{name}", &entry.name)
+                .replace("{status}", &entry.status);
+            let value: String = self.replace_color_placeholders(&format.replace("{name}", &entry.name).replace("{status}", &entry.status), config);
+
+            lines.push(Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value));
+        }
+
+        lines
+    }
+}
+
+pub fn get_cpu_security(config: &Configuration) -> Result<SecurityCPUInfo, ModuleError> {
+    let mut security: SecurityCPUInfo = SecurityCPUInfo::new();
+
+    // Each file in here is a vulnerability the kernel knows of, with the contents being the status
+    // https://docs.kernel.org/admin-guide/hw-vuln/index.html
+    let dir: ReadDir = match fs::read_dir("/sys/devices/system/cpu/vulnerabilities") {
+        Ok(r) => r,
+        Err(e) => return Err(ModuleError::new("SecurityCPU", format!("Can't read from /sys/devices/system/cpu/vulnerabilities: {e}"))),
+    };
+    for entry in dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let name: String = entry.file_name().to_string_lossy().to_string();
+        if config.security_cpu.ignore.contains(&name) {
+            continue;
+        }
+
+        let path: &Path = &entry.path();
+        let status: String = match util::file_read(path) {
+            Ok(r) => r.trim().to_string(),
+            Err(e) => return Err(ModuleError::new("SecurityCPU", format!("Can't read from {} - {e}", path.display()))),
+        };
+
+        let vuln: VulnerabilityEntry = VulnerabilityEntry {
+            name,
+            status
+        };
+        if vuln.is_vulnerable() {
+            security.vulnerable += 1;
+        } else if vuln.status.starts_with("Mitigation") {
+            security.mitigated += 1;
+        } else if vuln.status == "Not affected" {
+            security.not_affected += 1;
+        }
+        security.entries.push(vuln);
+    }
+
+    security.entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(security)
+}
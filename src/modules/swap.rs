@@ -1,13 +1,26 @@
 use core::str;
+use std::path::Path;
 
 use serde::Deserialize;
 
-use crate::{config_manager::Configuration, formatter::{self, CrabFetchColor}, module::Module, common_sources::syscalls::SyscallCache, ModuleError};
+use crate::{config_manager::Configuration, formatter::{self, CrabFetchColor}, module::Module, common_sources::syscalls::SyscallCache, util::{self, is_flag_set_u32}, ModuleError};
 
 pub struct SwapInfo {
     used_kb: u64,
     total_kb: u64,
-    percent: f32
+    percent: f32,
+    zswap: Option<bool>,
+    devices: Vec<SwapDevice>
+}
+pub struct SwapDevice {
+    name: String,       // /dev/zram0 / /swapfile
+    swap_type: String,  // partition / file / zram
+    used_kb: u64,
+    total_kb: u64,
+    percent: f32,
+    priority: i32,
+    compression_ratio: Option<f32>, // zram only
+    algorithm: Option<String>       // zram only
 }
 #[derive(Deserialize)]
 pub struct SwapConfiguration {
@@ -23,14 +36,19 @@ pub struct SwapConfiguration {
     pub progress_target_length: Option<u8>,
     pub decimal_places: Option<u32>,
     pub use_ibis: Option<bool>,
-    pub format: String
+    pub format: String,
+    pub list_devices: bool,
+    pub device_title: String,
+    pub device_format: String
 }
 impl Module for SwapInfo {
     fn new() -> SwapInfo {
         SwapInfo {
             used_kb: 0,
             total_kb: 0,
-            percent: 0.0
+            percent: 0.0,
+            zswap: None,
+            devices: Vec::new()
         }
     }
 
@@ -54,7 +72,9 @@ impl Module for SwapInfo {
         let title: String = config.uptime.title
             .replace("{used}", "Unknown")
             .replace("{total}", "Unknown")
-            .replace("{bar}", "Unknown");
+            .replace("{bar}", "Unknown")
+            .replace("{devices}", "Unknown")
+            .replace("{zswap}", "Unknown");
 
         Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
     }
@@ -65,12 +85,7 @@ impl Module for SwapInfo {
 
         let mut bar: String = String::new();
         if text.contains("{bar}") {
-            let left_border: &str = config.swap.progress_left_border.as_ref().unwrap_or(&config.progress_left_border);
-            let right_border: &str = config.swap.progress_right_border.as_ref().unwrap_or(&config.progress_right_border);
-            let progress: &str = config.swap.progress_progress.as_ref().unwrap_or(&config.progress_progress);
-            let empty: &str = config.swap.progress_empty.as_ref().unwrap_or(&config.progress_empty);
-            let length: u8 = config.swap.progress_target_length.unwrap_or(config.progress_target_length);
-            formatter::make_bar(&mut bar, left_border, right_border, progress, empty, self.percent, length);
+            make_swap_bar(&mut bar, self.percent, config);
         }
 
         #[allow(clippy::cast_possible_truncation)]
@@ -78,17 +93,105 @@ impl Module for SwapInfo {
             .replace("{used}", &formatter::auto_format_bytes(self.used_kb, use_ibis, dec_places))
             .replace("{total}", &formatter::auto_format_bytes(self.total_kb, use_ibis, dec_places))
             .replace("{bar}", &bar)
+            .replace("{devices}", &self.devices.len().to_string())
+            .replace("{zswap}", zswap_status(self.zswap))
     }
 
-    fn gen_info_flags(_: &str) -> u32 {
-        panic!("gen_info_flags called on swap module. This should never happen, please make a bug report!")
+    fn gen_info_flags(format: &str) -> u32 {
+        let mut info_flags: u32 = 0;
+
+        if format.contains("{devices}") {
+            info_flags |= SWAP_INFOFLAG_DEVICES;
+        }
+        if format.contains("{zswap}") {
+            info_flags |= SWAP_INFOFLAG_ZSWAP;
+        }
+        if format.contains("{compression_ratio}") || format.contains("{algorithm}") {
+            info_flags |= SWAP_INFOFLAG_ZRAM;
+        }
+
+        info_flags
     }
 }
+impl SwapInfo {
+    // Used when list_devices is on, styles each swap device onto it's own line
+    // Falls back to the regular output when there's no swap at all
+    pub fn style_devices(&self, config: &Configuration) -> Vec<(String, String)> {
+        if self.devices.is_empty() {
+            return vec![self.style(config)];
+        }
+
+        let title_color: &CrabFetchColor = config.swap.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.swap.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.swap.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.swap.separator.as_ref().unwrap_or(&config.separator);
 
-#[allow(clippy::unnecessary_wraps, clippy::cast_precision_loss)]
-pub fn get_swap(syscall_cache: &mut SyscallCache) -> Result<SwapInfo, ModuleError> {
+        let mut lines: Vec<(String, String)> = Vec::new();
+        for device in &self.devices {
+            let title: String = self.replace_device_placeholders(device, &config.swap.device_title, config);
+            let value: String = self.replace_color_placeholders(&self.replace_device_placeholders(device, &config.swap.device_format, config), config);
+            lines.push(Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value));
+        }
+
+        lines
+    }
+
+    fn replace_device_placeholders(&self, device: &SwapDevice, text: &str, config: &Configuration) -> String {
+        let dec_places: u32 = config.swap.decimal_places.unwrap_or(config.decimal_places);
+        let use_ibis: bool = config.swap.use_ibis.unwrap_or(config.use_ibis);
+
+        let mut bar: String = String::new();
+        if text.contains("{bar}") {
+            make_swap_bar(&mut bar, device.percent, config);
+        }
+        let compression_ratio: String = match device.compression_ratio {
+            Some(r) => format!("{}x", formatter::round(f64::from(r), dec_places)),
+            None => "N/A".to_string(),
+        };
+
+        #[allow(clippy::cast_possible_truncation)]
+        formatter::process_percentage_placeholder(text, formatter::round(f64::from(device.percent), dec_places) as f32, config)
+            .replace("{name}", &device.name)
+            .replace("{type}", &device.swap_type)
+            .replace("{used}", &formatter::auto_format_bytes(device.used_kb, use_ibis, dec_places))
+            .replace("{total}", &formatter::auto_format_bytes(device.total_kb, use_ibis, dec_places))
+            .replace("{bar}", &bar)
+            .replace("{priority}", &device.priority.to_string())
+            .replace("{compression_ratio}", &compression_ratio)
+            .replace("{algorithm}", device.algorithm.as_deref().unwrap_or("N/A"))
+            .replace("{zswap}", zswap_status(self.zswap))
+    }
+}
+
+fn make_swap_bar(bar: &mut String, percent: f32, config: &Configuration) {
+    let left_border: &str = config.swap.progress_left_border.as_ref().unwrap_or(&config.progress_left_border);
+    let right_border: &str = config.swap.progress_right_border.as_ref().unwrap_or(&config.progress_right_border);
+    let progress: &str = config.swap.progress_progress.as_ref().unwrap_or(&config.progress_progress);
+    let empty: &str = config.swap.progress_empty.as_ref().unwrap_or(&config.progress_empty);
+    let length: u8 = config.swap.progress_target_length.unwrap_or(config.progress_target_length);
+    formatter::make_bar(bar, left_border, right_border, progress, empty, percent, length);
+}
+fn zswap_status(zswap: Option<bool>) -> &'static str {
+    match zswap {
+        Some(true) => "Enabled",
+        Some(false) => "Disabled",
+        None => "Unavailable",
+    }
+}
+
+const SWAP_INFOFLAG_DEVICES: u32 = 1;
+const SWAP_INFOFLAG_ZSWAP: u32 = 2;
+const SWAP_INFOFLAG_ZRAM: u32 = 4;
+
+#[allow(clippy::cast_precision_loss)]
+pub fn get_swap(config: &Configuration, syscall_cache: &mut SyscallCache) -> Result<SwapInfo, ModuleError> {
     let mut swap: SwapInfo = SwapInfo::new();
-    // no info flags here as it's all dependent on eachother
+    // title is tagged onto the end here to account for the title placeholders
+    let mut info_flags: u32 = SwapInfo::gen_info_flags(&format!("{}{}", config.swap.format, config.swap.title));
+    if config.swap.list_devices {
+        info_flags |= SWAP_INFOFLAG_DEVICES;
+        info_flags |= SwapInfo::gen_info_flags(&format!("{}{}", config.swap.device_format, config.swap.device_title));
+    }
 
     let sysinfo: libc::sysinfo = syscall_cache.get_sysinfo_cached();
 
@@ -99,5 +202,87 @@ pub fn get_swap(syscall_cache: &mut SyscallCache) -> Result<SwapInfo, ModuleErro
         swap.percent = (swap.used_kb as f32 / swap.total_kb as f32) * 100.0;
     }
 
+    if is_flag_set_u32(info_flags, SWAP_INFOFLAG_DEVICES) {
+        swap.devices = get_swap_devices(is_flag_set_u32(info_flags, SWAP_INFOFLAG_ZRAM))?;
+    }
+    if is_flag_set_u32(info_flags, SWAP_INFOFLAG_ZSWAP) {
+        // Not existing means zswap isn't built into the kernel at all
+        swap.zswap = util::file_read(Path::new("/sys/module/zswap/parameters/enabled"))
+            .ok()
+            .map(|x| x.trim() == "Y");
+    }
+
     Ok(swap)
 }
+
+#[allow(clippy::cast_precision_loss)]
+fn get_swap_devices(zram_info: bool) -> Result<Vec<SwapDevice>, ModuleError> {
+    let mut devices: Vec<SwapDevice> = Vec::new();
+
+    // Filename                                Type            Size            Used            Priority
+    // /dev/zram0                              partition       8388604         0               100
+    let contents: String = match util::file_read(Path::new("/proc/swaps")) {
+        Ok(r) => r,
+        Err(e) => return Err(ModuleError::new("Swap", format!("Can't read from /proc/swaps - {e}"))),
+    };
+    for line in contents.lines().skip(1) {
+        let values: Vec<&str> = line.split_whitespace().collect();
+        if values.len() < 5 {
+            continue;
+        }
+
+        // Sizes are in KiB
+        let total_kb: u64 = values[2].parse::<u64>().unwrap_or(0) * 1024 / 1000;
+        let used_kb: u64 = values[3].parse::<u64>().unwrap_or(0) * 1024 / 1000;
+        // Spaces in file names are escaped
+        let name: String = values[0].replace("\\040", " ");
+
+        let mut device: SwapDevice = SwapDevice {
+            name,
+            swap_type: values[1].to_string(),
+            used_kb,
+            total_kb,
+            percent: 0.0,
+            priority: values[4].parse::<i32>().unwrap_or(0),
+            compression_ratio: None,
+            algorithm: None
+        };
+        if total_kb != 0 {
+            device.percent = (used_kb as f32 / total_kb as f32) * 100.0;
+        }
+
+        if let Some(zram) = device.name.strip_prefix("/dev/").filter(|x| x.starts_with("zram")) {
+            device.swap_type = "zram".to_string();
+            if zram_info {
+                let zram_path: &Path = &Path::new("/sys/block").join(zram);
+                device.compression_ratio = read_zram_ratio(zram_path);
+                device.algorithm = read_zram_algorithm(zram_path);
+            }
+        }
+
+        devices.push(device);
+    }
+
+    Ok(devices)
+}
+
+// https://docs.kernel.org/admin-guide/blockdev/zram.html#stats
+// The first two values are the uncompressed and compressed size of the stored data, in bytes
+#[allow(clippy::cast_precision_loss)]
+fn read_zram_ratio(path: &Path) -> Option<f32> {
+    let mm_stat: String = util::file_read(&path.join("mm_stat")).ok()?;
+    let mut values = mm_stat.split_whitespace();
+    let original: u64 = values.next()?.parse().ok()?;
+    let compressed: u64 = values.next()?.parse().ok()?;
+    if compressed == 0 {
+        return None;
+    }
+    Some(original as f32 / compressed as f32)
+}
+// Lists every available algorithm, with the active one in brackets, e.g "lzo lzo-rle [zstd]"
+fn read_zram_algorithm(path: &Path) -> Option<String> {
+    let algorithms: String = util::file_read(&path.join("comp_algorithm")).ok()?;
+    let start: usize = algorithms.find('[')?;
+    let end: usize = algorithms[start..].find(']')? + start;
+    Some(algorithms[start + 1..end].to_string())
+}
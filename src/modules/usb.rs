@@ -0,0 +1,147 @@
+use std::{fs::{self, ReadDir}, path::{Path, PathBuf}};
+
+use serde::Deserialize;
+
+use crate::{common_sources::{hwids, ids_index::IdsIndex}, config_manager::Configuration, formatter::CrabFetchColor, module::Module, util, ModuleError};
+
+pub struct USBInfo {
+    port: String,       // 1-2.3
+    vendor_id: String,  // 046d
+    product_id: String,
+    vendor: String,
+    product: String,
+    speed: String
+}
+#[derive(Deserialize)]
+pub struct USBConfiguration {
+    pub title: String,
+    pub title_color: Option<CrabFetchColor>,
+    pub title_bold: Option<bool>,
+    pub title_italic: Option<bool>,
+    pub separator: Option<String>,
+    pub format: String,
+    pub show_hubs: bool
+}
+impl Module for USBInfo {
+    fn new() -> USBInfo {
+        USBInfo {
+            port: "Unknown".to_string(),
+            vendor_id: "Unknown".to_string(),
+            product_id: "Unknown".to_string(),
+            vendor: "Unknown".to_string(),
+            product: "Unknown".to_string(),
+            speed: "Unknown".to_string()
+        }
+    }
+
+    fn style(&self, config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.usb.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.usb.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.usb.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.usb.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = self.replace_placeholders(&config.usb.title, config);
+        let value: String = self.replace_color_placeholders(&self.replace_placeholders(&config.usb.format, config), config);
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value)
+    }
+    fn unknown_output(config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.usb.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.usb.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.usb.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.usb.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = config.usb.title
+            .replace("{port}", "Unknown")
+            .replace("{vendor_id}", "Unknown")
+            .replace("{product_id}", "Unknown")
+            .replace("{vendor}", "Unknown")
+            .replace("{product}", "Unknown")
+            .replace("{speed}", "Unknown");
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
+    }
+
+    fn replace_placeholders(&self, text: &str, _: &Configuration) -> String {
+        text.replace("{port}", &self.port)
+            .replace("{vendor_id}", &self.vendor_id)
+            .replace("{product_id}", &self.product_id)
+            .replace("{vendor}", &self.vendor)
+            .replace("{product}", &self.product)
+            .replace("{speed}", &self.speed)
+    }
+
+    fn gen_info_flags(_: &str) -> u32 {
+        panic!("gen_info_flags called on USB module. This should never happen, please make a bug report!")
+    }
+}
+
+pub fn get_usb_devices(config: &Configuration) -> Result<Vec<USBInfo>, ModuleError> {
+    let mut devices: Vec<USBInfo> = Vec::new();
+    // Opened once up front, rather than for every device
+    let ids: Option<IdsIndex> = hwids::find_usb_ids().and_then(|x| hwids::open_ids(&x).ok());
+
+    // https://www.kernel.org/doc/Documentation/ABI/stable/sysfs-bus-usb
+    let dir: ReadDir = match fs::read_dir("/sys/bus/usb/devices") {
+        Ok(r) => r,
+        Err(e) => return Err(ModuleError::new("USB", format!("Can't read from /sys/bus/usb/devices: {e}"))),
+    };
+    for entry in dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let name: String = entry.file_name().to_string_lossy().to_string();
+        // Interfaces of a device are listed alongside it, e.g 1-2:1.0
+        if name.contains(':') {
+            continue;
+        }
+        let path: PathBuf = entry.path();
+
+        // Root hubs (usb1, usb2...) and any other hubs
+        // https://www.usb.org/defined-class-codes
+        let is_hub: bool = name.starts_with("usb") || read_value(&path, "bDeviceClass").is_some_and(|x| x == "09");
+        if is_hub && !config.usb.show_hubs {
+            continue;
+        }
+
+        let mut device: USBInfo = USBInfo::new();
+        if let Some(vendor_id) = read_value(&path, "idVendor") {
+            device.vendor_id = vendor_id;
+        }
+        if let Some(product_id) = read_value(&path, "idProduct") {
+            device.product_id = product_id;
+        }
+        if let Some(speed) = read_value(&path, "speed") {
+            device.speed = format!("{speed} Mbps");
+        }
+
+        // usb.ids first, as devices can report some pretty useless names for themselves
+        if let Some((vendor, product)) = ids.as_ref().map(|x| hwids::search_ids(x, &device.vendor_id, &device.product_id)) {
+            device.vendor = vendor;
+            if product != device.product_id {
+                device.product = product;
+            }
+        }
+        if device.vendor.is_empty() || device.vendor == "Unknown" {
+            device.vendor = read_value(&path, "manufacturer").unwrap_or(device.vendor_id.clone());
+        }
+        if device.product == "Unknown" {
+            device.product = read_value(&path, "product").unwrap_or(device.product_id.clone());
+        }
+
+        device.port = name;
+        devices.push(device);
+    }
+
+    devices.sort_by(|a, b| a.port.cmp(&b.port));
+    Ok(devices)
+}
+
+fn read_value(path: &Path, file: &str) -> Option<String> {
+    let value: String = util::file_read(&path.join(file)).ok()?;
+    let value: &str = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    Some(value.to_string())
+}
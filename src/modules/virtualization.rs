@@ -0,0 +1,93 @@
+use serde::Deserialize;
+
+use crate::{common_sources::virtualization, config_manager::Configuration, formatter::CrabFetchColor, module::Module, util::is_flag_set_u32, ModuleError};
+
+pub struct VirtualizationInfo {
+    hypervisor: Option<String>,
+    container: Option<String>
+}
+#[derive(Deserialize)]
+pub struct VirtualizationConfiguration {
+    pub title: String,
+    pub title_color: Option<CrabFetchColor>,
+    pub title_bold: Option<bool>,
+    pub title_italic: Option<bool>,
+    pub separator: Option<String>,
+    pub format: String
+}
+impl Module for VirtualizationInfo {
+    fn new() -> VirtualizationInfo {
+        VirtualizationInfo {
+            hypervisor: None,
+            container: None
+        }
+    }
+
+    fn style(&self, config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.virtualization.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.virtualization.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.virtualization.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.virtualization.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = self.replace_placeholders(&config.virtualization.title, config);
+        let value: String = self.replace_color_placeholders(&self.replace_placeholders(&config.virtualization.format, config), config);
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, &value)
+    }
+    fn unknown_output(config: &Configuration) -> (String, String) {
+        let title_color: &CrabFetchColor = config.virtualization.title_color.as_ref().unwrap_or(&config.title_color);
+        let title_bold: bool = config.virtualization.title_bold.unwrap_or(config.title_bold);
+        let title_italic: bool = config.virtualization.title_italic.unwrap_or(config.title_italic);
+        let separator: &str = config.virtualization.separator.as_ref().unwrap_or(&config.separator);
+
+        let title: String = config.virtualization.title
+            .replace("{hypervisor}", "Unknown")
+            .replace("{container}", "Unknown")
+            .replace("{virtualization}", "Unknown");
+
+        Self::default_style(config, &title, title_color, title_bold, title_italic, separator, "Unknown")
+    }
+
+    fn replace_placeholders(&self, text: &str, _: &Configuration) -> String {
+        // Containers take priority, as they're the more immediate environment we're in
+        let virtualization: &str = self.container.as_deref()
+            .or(self.hypervisor.as_deref())
+            .unwrap_or("None");
+
+        text.replace("{hypervisor}", self.hypervisor.as_deref().unwrap_or("None"))
+            .replace("{container}", self.container.as_deref().unwrap_or("None"))
+            .replace("{virtualization}", virtualization)
+    }
+
+    fn gen_info_flags(format: &str) -> u32 {
+        let mut info_flags: u32 = 0;
+
+        if format.contains("{hypervisor}") || format.contains("{virtualization}") {
+            info_flags |= VIRTUALIZATION_INFOFLAG_HYPERVISOR;
+        }
+        if format.contains("{container}") || format.contains("{virtualization}") {
+            info_flags |= VIRTUALIZATION_INFOFLAG_CONTAINER;
+        }
+
+        info_flags
+    }
+}
+
+const VIRTUALIZATION_INFOFLAG_HYPERVISOR: u32 = 1;
+const VIRTUALIZATION_INFOFLAG_CONTAINER: u32 = 2;
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn get_virtualization(config: &Configuration) -> Result<VirtualizationInfo, ModuleError> {
+    let mut virt: VirtualizationInfo = VirtualizationInfo::new();
+    // title is tagged onto the end here to account for the title placeholders
+    let info_flags: u32 = VirtualizationInfo::gen_info_flags(&format!("{}{}", config.virtualization.format, config.virtualization.title));
+
+    if is_flag_set_u32(info_flags, VIRTUALIZATION_INFOFLAG_HYPERVISOR) {
+        virt.hypervisor = virtualization::detect_hypervisor();
+    }
+    if is_flag_set_u32(info_flags, VIRTUALIZATION_INFOFLAG_CONTAINER) {
+        virt.container = virtualization::detect_container();
+    }
+
+    Ok(virt)
+}
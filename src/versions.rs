@@ -80,13 +80,17 @@ fn parse_command(path: &str, name: &str) -> Option<String> {
         Err(_) => return None,
     };
 
+    parse_command_output(&raw, name)
+}
+fn parse_command_output(raw: &str, name: &str) -> Option<String> {
     // Fixes for different outputs
     // Warning: Messy 1-liners
     match name {
         "bash" => Some(raw.split(' ').collect::<Vec<&str>>()[3].split('(').next().unwrap().trim().to_string()),
         "elvish" => Some(raw.split('+').collect::<Vec<&str>>()[0].trim().to_string()),
-        "foot" | "fish" => Some(raw.split(' ').collect::<Vec<&str>>()[2].trim().to_string()),
+        "foot" | "fish" | "jackd" => Some(raw.split(' ').collect::<Vec<&str>>()[2].trim().to_string()),
         "nvim" => Some(raw.split(' ').collect::<Vec<&str>>()[1].split('\n').next().unwrap()[1..].to_string()),
+        "pipewire" | "pipewire-pulse" => Some(raw.split_whitespace().last()?.to_string()),
         "systemd" => Some(raw.split(' ').collect::<Vec<&str>>()[2].split('\n').next().unwrap().trim_matches(['(', ')']).to_string()),
         "vim" => Some(raw.split(' ').collect::<Vec<&str>>()[4].to_string()),
         "xterm" => Some(raw.split('(').collect::<Vec<&str>>()[1].split(')').next().unwrap().to_string()),
@@ -154,3 +158,18 @@ fn nushell_version() -> Option<String> {
         Err(_) => None,
     }
 }
+
+
+mod tests {
+    #[test]
+    fn test_parse_command_output() {
+        use crate::versions::parse_command_output;
+
+        // pipewire-pulse --version
+        let pipewire: &str = "pipewire-pulse\nCompiled with libpipewire 1.2.7\nLinked with libpipewire 1.2.7";
+        assert_eq!(parse_command_output(pipewire, "pipewire-pulse").as_deref(), Some("1.2.7"));
+        assert_eq!(parse_command_output(&pipewire.replacen("pipewire-pulse", "pipewire", 1), "pipewire").as_deref(), Some("1.2.7"));
+        // jackd --version
+        assert_eq!(parse_command_output("jackdmp version 1.9.22 tmpdir /dev/shm protocol 9", "jackd").as_deref(), Some("1.9.22"));
+    }
+}